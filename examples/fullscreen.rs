@@ -1,7 +1,7 @@
 extern crate winit;
 
 use std::io::{self, Write};
-use winit::{ControlFlow, Event, WindowEvent};
+use winit::{ControlFlow, Event, Fullscreen, WindowEvent};
 
 fn main() {
     let mut events_loop = winit::EventsLoop::new();
@@ -25,9 +25,29 @@ fn main() {
         monitor
     };
 
+    // picking a video mode, or keeping the current one
+    let fullscreen = {
+        let modes: Vec<_> = monitor.video_modes().collect();
+        for (num, mode) in modes.iter().enumerate() {
+            let (width, height): (u32, u32) = mode.get_dimensions().into();
+            println!("Mode #{}: {}x{} {}Hz {}bpp", num, width, height,
+                     mode.get_refresh_rate(), mode.get_bit_depth());
+        }
+
+        print!("Please write the number of the video mode to use, or nothing to keep the current one: ");
+        io::stdout().flush().unwrap();
+
+        let mut num = String::new();
+        io::stdin().read_line(&mut num).unwrap();
+        match num.trim().parse::<usize>().ok() {
+            Some(num) => Fullscreen::Exclusive(modes.get(num).expect("Please enter a valid ID").clone()),
+            None => Fullscreen::Borderless(monitor),
+        }
+    };
+
     let _window = winit::WindowBuilder::new()
         .with_title("Hello world!")
-        .with_fullscreen(Some(monitor))
+        .with_fullscreen(Some(fullscreen))
         .build(&events_loop)
         .unwrap();
 
@@ -1,11 +1,13 @@
 extern crate winit;
 
+use winit::dpi::LogicalSize;
+
 fn main() {
     let mut events_loop = winit::EventsLoop::new();
 
     let _window = winit::WindowBuilder::new()
-        .with_min_dimensions(400, 200)
-        .with_max_dimensions(800, 400)
+        .with_min_dimensions(LogicalSize::new(400.0, 200.0))
+        .with_max_dimensions(LogicalSize::new(800.0, 400.0))
         .build(&events_loop)
         .unwrap();
 
@@ -0,0 +1,42 @@
+extern crate winit;
+
+#[derive(Debug)]
+enum CustomEvent {
+    Tick(u32),
+}
+
+fn main() {
+    let mut events_loop = winit::EventsLoop::<CustomEvent>::with_user_event();
+
+    let _window = winit::WindowBuilder::new()
+        .with_title("A fantastic window!")
+        .build(&events_loop)
+        .unwrap();
+
+    let proxy = events_loop.create_proxy();
+
+    std::thread::spawn(move || {
+        // Send a custom event to the `events_loop` once every second.
+        let mut counter = 0;
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            counter += 1;
+            if proxy.send_event(CustomEvent::Tick(counter)).is_err() {
+                break;
+            }
+        }
+    });
+
+    events_loop.run_forever(|event| {
+        println!("{:?}", event);
+        match event {
+            winit::Event::UserEvent(CustomEvent::Tick(count)) => {
+                println!("Tick number {}", count);
+                winit::ControlFlow::Continue
+            },
+            winit::Event::WindowEvent { event: winit::WindowEvent::Closed, .. } =>
+                winit::ControlFlow::Break,
+            _ => winit::ControlFlow::Continue,
+        }
+    });
+}
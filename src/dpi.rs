@@ -0,0 +1,158 @@
+//! Sizes and positions, in logical or physical pixels.
+//!
+//! Physical pixels are the pixels of the monitor. Logical pixels are physical pixels divided by
+//! the DPI factor of the window or monitor (see `Window::hidpi_factor`), so that a window with
+//! the same logical size looks roughly the same on a low DPI and on a high DPI monitor.
+//!
+//! Windows are sized and positioned in logical pixels, and the events that report sizes and
+//! positions use logical pixels as well. Monitors are described in physical pixels. Convert with
+//! `to_physical` and `to_logical` using the DPI factor of the window or monitor, for example to
+//! get the size of the framebuffer to pass to `glViewport`:
+//!
+//! ```no_run
+//! # let events_loop = winit::EventsLoop::new();
+//! # let window = winit::Window::new(&events_loop).unwrap();
+//! let size = window.get_inner_size().unwrap().to_physical(window.hidpi_factor());
+//! let (width, height): (u32, u32) = size.into();
+//! ```
+//!
+//! The DPI factor of a window can change while it is moved between monitors, in which case a
+//! `WindowEvent::HiDPIFactorChanged` is sent.
+
+/// A position in logical pixels.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogicalPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl LogicalPosition {
+    #[inline]
+    pub fn new(x: f64, y: f64) -> LogicalPosition {
+        LogicalPosition { x: x, y: y }
+    }
+
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalPosition>>(physical: T, dpi_factor: f64) -> LogicalPosition {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical(&self, dpi_factor: f64) -> PhysicalPosition {
+        PhysicalPosition::new(self.x * dpi_factor, self.y * dpi_factor)
+    }
+}
+
+/// A position in physical pixels.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicalPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl PhysicalPosition {
+    #[inline]
+    pub fn new(x: f64, y: f64) -> PhysicalPosition {
+        PhysicalPosition { x: x, y: y }
+    }
+
+    #[inline]
+    pub fn from_logical<T: Into<LogicalPosition>>(logical: T, dpi_factor: f64) -> PhysicalPosition {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical(&self, dpi_factor: f64) -> LogicalPosition {
+        LogicalPosition::new(self.x / dpi_factor, self.y / dpi_factor)
+    }
+}
+
+/// A size in logical pixels.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogicalSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl LogicalSize {
+    #[inline]
+    pub fn new(width: f64, height: f64) -> LogicalSize {
+        LogicalSize { width: width, height: height }
+    }
+
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalSize>>(physical: T, dpi_factor: f64) -> LogicalSize {
+        physical.into().to_logical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_physical(&self, dpi_factor: f64) -> PhysicalSize {
+        PhysicalSize::new(self.width * dpi_factor, self.height * dpi_factor)
+    }
+}
+
+/// A size in physical pixels.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicalSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl PhysicalSize {
+    #[inline]
+    pub fn new(width: f64, height: f64) -> PhysicalSize {
+        PhysicalSize { width: width, height: height }
+    }
+
+    #[inline]
+    pub fn from_logical<T: Into<LogicalSize>>(logical: T, dpi_factor: f64) -> PhysicalSize {
+        logical.into().to_physical(dpi_factor)
+    }
+
+    #[inline]
+    pub fn to_logical(&self, dpi_factor: f64) -> LogicalSize {
+        LogicalSize::new(self.width / dpi_factor, self.height / dpi_factor)
+    }
+}
+
+// Conversions from and to tuples. Converting to integers rounds to the nearest pixel.
+macro_rules! impl_tuple_conversions {
+    ($ty:ident, $a:ident, $b:ident, $int:ty) => {
+        impl From<(f64, f64)> for $ty {
+            #[inline]
+            fn from((a, b): (f64, f64)) -> $ty {
+                $ty { $a: a, $b: b }
+            }
+        }
+
+        impl From<($int, $int)> for $ty {
+            #[inline]
+            fn from((a, b): ($int, $int)) -> $ty {
+                $ty { $a: a as f64, $b: b as f64 }
+            }
+        }
+
+        impl Into<(f64, f64)> for $ty {
+            #[inline]
+            fn into(self) -> (f64, f64) {
+                (self.$a, self.$b)
+            }
+        }
+
+        impl Into<($int, $int)> for $ty {
+            #[inline]
+            fn into(self) -> ($int, $int) {
+                (self.$a.round() as $int, self.$b.round() as $int)
+            }
+        }
+    };
+}
+
+impl_tuple_conversions!(LogicalPosition, x, y, i32);
+impl_tuple_conversions!(PhysicalPosition, x, y, i32);
+impl_tuple_conversions!(LogicalSize, width, height, u32);
+impl_tuple_conversions!(PhysicalSize, width, height, u32);
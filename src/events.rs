@@ -1,9 +1,12 @@
 use std::path::PathBuf;
-use {WindowId, DeviceId};
+use {WindowId, DeviceId, MonitorId};
+use dpi::{LogicalPosition, LogicalSize};
 
 /// Describes a generic event.
+///
+/// The `T` parameter is the type of the user events sent with `EventsLoopProxy::send_event`.
 #[derive(Clone, Debug)]
-pub enum Event {
+pub enum Event<T = ()> {
     WindowEvent {
         window_id: WindowId,
         event: WindowEvent,
@@ -14,21 +17,136 @@ pub enum Event {
     },
     Awakened,
 
+    /// An event sent from another thread with `EventsLoopProxy::send_event`.
+    UserEvent(T),
+
+    /// A file descriptor registered with `EventsLoopExt::register_fd` is ready.
+    ///
+    /// `token` is the value that was given when registering the file descriptor. The file
+    /// descriptor is watched in a level-triggered way: this event is generated again as long as
+    /// the file descriptor stays ready.
+    ///
+    /// Only generated on Linux.
+    FdReady { token: usize, readiness: Readiness },
+
+    /// A signal watched with `EventsLoopExt::watch_signals` has been received.
+    ///
+    /// Only generated on Linux.
+    Signal(Signal),
+
+    /// The deadline requested by returning `ControlFlow::WaitUntil` or `ControlFlow::Poll` has
+    /// been reached without any other event being received.
+    ResumeTimeReached,
+
+    /// A new batch of events is about to be delivered.
+    ///
+    /// Sent before the first event of each batch. A batch contains the events that were available
+    /// when the events loop woke up, and ends with `EventsCleared`.
+    NewEvents,
+
+    /// All the events of the current batch have been delivered.
+    ///
+    /// This is a good time to update the application and render a frame. When using `run_forever`,
+    /// the value returned for this event decides how the events loop waits for the next batch.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - On Android, iOS and Emscripten, `run_forever` polls for events every few milliseconds,
+    ///   and each poll is a batch.
+    EventsCleared,
+
+    /// `run_forever` is about to return. This is the last event received by its callback.
+    LoopDestroyed,
+
+    /// A monitor has been connected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only generated on X11 and Wayland. On X11, the monitor events require the XRandR
+    ///   extension, and aren't generated if the X server doesn't have it.
+    /// - The monitors that are connected when the events loop is created are not reported.
+    MonitorAdded(MonitorId),
+
+    /// A monitor has been disconnected.
+    ///
+    /// The `MonitorId` describes the monitor as it was before being disconnected. Windows that
+    /// were made fullscreen on it should be moved to another monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only generated on X11 and Wayland.
+    MonitorRemoved(MonitorId),
+
+    /// The resolution, position, orientation or DPI factor of a monitor has changed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only generated on X11 and Wayland.
+    MonitorChanged(MonitorId),
+
+    /// The connection to the display server has been lost.
+    ///
+    /// This is the last event generated by the events loop: `run_forever` returns after it, and
+    /// the other functions return without doing anything. The windows can't be used anymore and
+    /// should be dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only generated on Linux.
+    /// - On X11, the loss is only reported if the events loop notices it while waiting for or
+    ///   polling events. If Xlib notices it first, for example during a call to a method of
+    ///   `Window`, it terminates the process, and this can't be prevented.
+    ConnectionLost,
+
     /// The application has been suspended or resumed.
     ///
     /// The parameter is true if app was suspended, and false if it has been resumed.
     Suspended(bool),
 }
 
+impl Event {
+    // Converts an event generated by a backend to an event with any user event type.
+    //
+    // Backends never generate `UserEvent`s, for which `None` is returned.
+    pub(crate) fn map_nonuser_event<T>(self) -> Option<Event<T>> {
+        match self {
+            Event::WindowEvent { window_id, event } => Some(Event::WindowEvent {
+                window_id: window_id,
+                event: event,
+            }),
+            Event::DeviceEvent { device_id, event } => Some(Event::DeviceEvent {
+                device_id: device_id,
+                event: event,
+            }),
+            Event::Awakened => Some(Event::Awakened),
+            Event::ResumeTimeReached => Some(Event::ResumeTimeReached),
+            Event::ConnectionLost => Some(Event::ConnectionLost),
+            Event::NewEvents => Some(Event::NewEvents),
+            Event::EventsCleared => Some(Event::EventsCleared),
+            Event::LoopDestroyed => Some(Event::LoopDestroyed),
+            Event::MonitorAdded(monitor) => Some(Event::MonitorAdded(monitor)),
+            Event::MonitorRemoved(monitor) => Some(Event::MonitorRemoved(monitor)),
+            Event::MonitorChanged(monitor) => Some(Event::MonitorChanged(monitor)),
+            Event::Suspended(suspended) => Some(Event::Suspended(suspended)),
+            Event::FdReady { token, readiness } => Some(Event::FdReady {
+                token: token,
+                readiness: readiness,
+            }),
+            Event::Signal(signal) => Some(Event::Signal(signal)),
+            Event::UserEvent(()) => None,
+        }
+    }
+}
+
 /// Describes an event from a `Window`.
 #[derive(Clone, Debug)]
 pub enum WindowEvent {
 
-    /// The size of the window has changed.
-    Resized(u32, u32),
+    /// The size of the client area of the window has changed.
+    Resized(LogicalSize),
 
     /// The position of the window has changed.
-    Moved(i32, i32),
+    Moved(LogicalPosition),
 
     /// The window has been closed.
     Closed,
@@ -50,15 +168,27 @@ pub enum WindowEvent {
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
     Focused(bool),
 
+    /// The window has been maximized, minimized, made fullscreen, focused, shown or hidden, or
+    /// the opposite.
+    ///
+    /// Contains the new state of the window, which is also returned by `Window::is_maximized` and
+    /// the similar methods. Sent whether the change was requested by the application or made by
+    /// the window manager.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only generated on X11 and Wayland.
+    StateChanged(WindowState),
+
     /// An event from the keyboard has been received.
     KeyboardInput { device_id: DeviceId, input: KeyboardInput },
 
     /// The cursor has moved on the window.
     ///
-    /// `position` is (x,y) coords in pixels relative to the top-left corner of the window. Because the range of this
-    /// data is limited by the display area and it may have been transformed by the OS to implement effects such as
-    /// mouse acceleration, it should not be used to implement non-cursor-like interactions such as 3D camera control.
-    MouseMoved { device_id: DeviceId, position: (f64, f64) },
+    /// `position` is relative to the top-left corner of the window. Because the range of this data is limited by the
+    /// display area and it may have been transformed by the OS to implement effects such as mouse acceleration, it
+    /// should not be used to implement non-cursor-like interactions such as 3D camera control.
+    MouseMoved { device_id: DeviceId, position: LogicalPosition },
 
     /// The cursor has entered the window.
     MouseEntered { device_id: DeviceId },
@@ -83,8 +213,29 @@ pub enum WindowEvent {
     AxisMotion { device_id: DeviceId, axis: AxisId, value: f64 },
 
     /// The window needs to be redrawn.
+    ///
+    /// Generated by the system, for example when a part of the window that was hidden becomes
+    /// visible. A `RedrawRequested` event is also generated at the end of the batch.
     Refresh,
 
+    /// The content of some parts of the window has been lost and must be redrawn.
+    ///
+    /// Contains the damaged region of the window, as a list of rectangles. Always followed by a
+    /// `Refresh` event.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only generated on X11 and Wayland. Wayland always reports the whole surface as damaged.
+    Damaged(Vec<Rect>),
+
+    /// The window should be redrawn.
+    ///
+    /// Delivered at the end of the batch of events, after all the input events, when
+    /// `Window::request_redraw` has been called or the system asked for the window to be redrawn
+    /// with `Refresh`. Delivered at most once per window and batch, no matter how many times the
+    /// redraw was requested.
+    RedrawRequested,
+
     /// Touch event has been received
     Touch(Touch),
 
@@ -95,7 +246,10 @@ pub enum WindowEvent {
     /// * A user changes the resolution.
     /// * A user changes the desktop scaling value (e.g. in Control Panel on Windows).
     /// * A user moves the application window to a display with a different DPI.
-    HiDPIFactorChanged(f32),
+    ///
+    /// If the size of the window in logical pixels changes as a result, for example on X11 where
+    /// windows keep their size in physical pixels, a `Resized` event follows. See the `dpi` module.
+    HiDPIFactorChanged(f64),
 }
 
 /// Represents raw hardware events that are not associated with any particular window.
@@ -107,6 +261,7 @@ pub enum WindowEvent {
 ///
 /// Note that these events are delivered regardless of input focus.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeviceEvent {
     Added,
     Removed,
@@ -119,8 +274,36 @@ pub enum DeviceEvent {
     Text { codepoint: char },
 }
 
+/// Describes the readiness of a file descriptor.
+///
+/// When registering a file descriptor, `readable` and `writable` indicate which readiness to
+/// watch for, and `error` is ignored. Errors and hang-ups are always reported.
+#[derive(Default, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Readiness {
+    /// The file descriptor can be read from without blocking.
+    pub readable: bool,
+    /// The file descriptor can be written to without blocking.
+    pub writable: bool,
+    /// An error or a hang-up happened on the file descriptor.
+    pub error: bool,
+}
+
+/// A Unix signal.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Signal {
+    /// `SIGINT`, usually sent when the user presses Ctrl-C in a terminal.
+    Interrupt,
+    /// `SIGTERM`, a request to terminate the process.
+    Terminate,
+    /// `SIGHUP`, sent when the controlling terminal is closed.
+    Hangup,
+}
+
 /// Describes a keyboard input event.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyboardInput {
     /// Identifies the physical key pressed
     ///
@@ -146,6 +329,7 @@ pub struct KeyboardInput {
 
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TouchPhase {
     Started,
     Moved,
@@ -172,11 +356,24 @@ pub enum TouchPhase {
 pub struct Touch {
     pub device_id: DeviceId,
     pub phase: TouchPhase,
-    pub location: (f64,f64),
+    /// Relative to the top-left corner of the window.
+    pub location: LogicalPosition,
     /// unique identifier of a finger.
     pub id: u64
 }
 
+/// A rectangle of a window, in physical pixels.
+///
+/// The position is relative to the top-left hand corner of the client area of the window.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
 /// Hardware-dependent keyboard scan code.
 pub type ScanCode = u32;
 
@@ -188,6 +385,7 @@ pub type ButtonId = u32;
 
 /// Describes the input state of a key.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ElementState {
     Pressed,
     Released,
@@ -195,6 +393,7 @@ pub enum ElementState {
 
 /// Describes a button of a mouse controller.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseButton {
     Left,
     Right,
@@ -204,6 +403,7 @@ pub enum MouseButton {
 
 /// Describes a difference in the mouse scroll wheel state.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseScrollDelta {
 	/// Amount in lines or rows to scroll in the horizontal
 	/// and vertical directions.
@@ -222,6 +422,7 @@ pub enum MouseScrollDelta {
 
 /// Symbolic name for a keyboard key.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(u32)]
 pub enum VirtualKeyCode {
     /// The '1' key over the letters.
@@ -410,6 +611,7 @@ pub enum VirtualKeyCode {
 ///
 /// Each field of this struct represents a modifier and is `true` if this modifier is active.
 #[derive(Default, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ModifiersState {
     /// The "shift" key
     pub shift: bool,
@@ -422,3 +624,23 @@ pub struct ModifiersState {
     /// This is the "windows" key on PC and "command" key on Mac.
     pub logo: bool
 }
+
+/// Represents the state of a window, as set by the application or by the window manager.
+///
+/// See `WindowEvent::StateChanged`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowState {
+    /// The window is maximized.
+    pub maximized: bool,
+    /// The window is minimized, also called iconified.
+    pub minimized: bool,
+    /// The window covers a monitor, see `Window::set_fullscreen`.
+    pub fullscreen: bool,
+    /// The window has the keyboard focus.
+    pub focused: bool,
+    /// The window is shown, see `Window::show` and `Window::hide`.
+    ///
+    /// A minimized window is still considered as visible.
+    pub visible: bool,
+}
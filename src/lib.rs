@@ -41,8 +41,8 @@
 //! loop {
 //!     events_loop.poll_events(|event| {
 //!         match event {
-//!             Event::WindowEvent { event: WindowEvent::Resized(w, h), .. } => {
-//!                 println!("The window was resized to {}x{}", w, h);
+//!             Event::WindowEvent { event: WindowEvent::Resized(size), .. } => {
+//!                 println!("The window was resized to {}x{}", size.width, size.height);
 //!             },
 //!             _ => ()
 //!         }
@@ -82,6 +82,9 @@
 
 #[macro_use]
 extern crate lazy_static;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
 
 extern crate libc;
 
@@ -110,14 +113,25 @@ extern crate x11_dl;
 #[macro_use]
 extern crate wayland_client;
 
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::{Arc, Mutex, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
 pub use events::*;
-pub use window::{AvailableMonitorsIter, MonitorId};
+pub use window::{AvailableMonitorsIter, Fullscreen, GammaRamp, MonitorId, MonitorTransform, SubpixelOrder,
+                 VideoMode, VideoModesIter};
+
+use dpi::LogicalSize;
 
 mod platform;
 mod events;
 mod window;
 
+pub mod dpi;
 pub mod os;
+pub mod record;
 
 /// Represents a window.
 ///
@@ -140,6 +154,9 @@ pub mod os;
 /// ```
 pub struct Window {
     window: platform::Window,
+    // Shared with the `EventsLoop`, see `Window::request_redraw`.
+    redraw_requests: Weak<Mutex<Vec<WindowId>>>,
+    events_loop_proxy: platform::EventsLoopProxy,
 }
 
 /// Identifier of a window. Unique for each window.
@@ -168,22 +185,49 @@ pub struct DeviceId(platform::DeviceId);
 ///
 /// To wake up an `EventsLoop` from a another thread, see the `EventsLoopProxy` docs.
 ///
+/// The `T` parameter is the type of the user events that can be sent to the `EventsLoop` with
+/// `EventsLoopProxy::send_event`. It defaults to `()`; use `EventsLoop::with_user_event()` to
+/// build an events loop that receives another type.
+///
 /// Note that the `EventsLoop` cannot be shared accross threads (due to platform-dependant logic
 /// forbiding it), as such it is neither `Send` nor `Sync`. If you need cross-thread access, the
 /// `Window` created from this `EventsLoop` _can_ be sent to an other thread, and the
 /// `EventsLoopProxy` allows you to wakeup an `EventsLoop` from an other thread.
-pub struct EventsLoop {
+pub struct EventsLoop<T = ()> {
     events_loop: platform::EventsLoop,
+    user_events: Arc<Mutex<VecDeque<T>>>,
+    pending_wakeup: Arc<AtomicBool>,
+    // The windows for which a `RedrawRequested` event must be delivered at the end of the batch.
+    redraw_requests: Arc<Mutex<Vec<WindowId>>>,
+    // See `set_coalescing`.
+    coalesce: bool,
     _marker: ::std::marker::PhantomData<*mut ()> // Not Send nor Sync
 }
 
 /// Returned by the user callback given to the `EventsLoop::run_forever` method.
 ///
-/// Indicates whether the `run_forever` method should continue or complete.
+/// Indicates whether the `run_forever` method should continue or complete, and how it should wait
+/// for new events. The value returned for the last event that was received decides how the
+/// events loop waits before the next one.
+///
+/// ## Platform-specific
+///
+/// - `Poll` and `WaitUntil` are only implemented on X11, Wayland and Windows. Other platforms
+///   treat them like `Continue`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ControlFlow {
     /// Continue looping and waiting for events.
     Continue,
+    /// Continue looping without waiting for events.
+    ///
+    /// If no event is pending, an `Event::ResumeTimeReached` is generated immediately. This is
+    /// suitable for applications that render continuously.
+    Poll,
+    /// Continue looping and wait for events until the given instant.
+    ///
+    /// If no event has been received when the deadline is reached, an `Event::ResumeTimeReached`
+    /// is generated.
+    WaitUntil(Instant),
     /// Break from the event loop.
     Break,
 }
@@ -192,12 +236,51 @@ impl EventsLoop {
     /// Builds a new events loop.
     ///
     /// Usage will result in display backend initialisation, this can be controlled on linux
-    /// using an environment variable `WINIT_UNIX_BACKEND`. Legal values are `x11` and `wayland`.
-    /// If it is not set, winit will try to connect to a wayland connection, and if it fails will
+    /// using an environment variable `WINIT_UNIX_BACKEND`. Legal values are `x11`, `wayland` and
+    /// `headless`. If it is not set, winit will try to connect to a wayland connection, and if it fails will
     /// fallback on x11. If this variable is set with any other value, winit will panic.
+    ///
+    /// Panics if no backend can be initialized. See `try_new` for a version that returns an error
+    /// instead.
     pub fn new() -> EventsLoop {
+        EventsLoop::with_user_event()
+    }
+
+    /// Builds a new events loop, or returns an error if no backend can be initialized.
+    ///
+    /// See the documentation of `new` for details about the choice of the backend.
+    pub fn try_new() -> Result<EventsLoop, EventsLoopCreationError> {
+        EventsLoop::try_with_user_event()
+    }
+}
+
+impl<T> EventsLoop<T> {
+    /// Builds a new events loop that can receive user events of type `T`.
+    ///
+    /// User events are sent with `EventsLoopProxy::send_event` and are received as
+    /// `Event::UserEvent`. See the documentation of `EventsLoop::new` for details about the
+    /// initialisation of the backend.
+    ///
+    /// Panics if no backend can be initialized.
+    pub fn with_user_event() -> EventsLoop<T> {
+        match EventsLoop::try_with_user_event() {
+            Ok(events_loop) => events_loop,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Same as `with_user_event`, but returns an error if no backend can be initialized.
+    pub fn try_with_user_event() -> Result<EventsLoop<T>, EventsLoopCreationError> {
+        platform::EventsLoop::try_new().map(EventsLoop::from_platform)
+    }
+
+    pub(crate) fn from_platform(events_loop: platform::EventsLoop) -> EventsLoop<T> {
         EventsLoop {
-            events_loop: platform::EventsLoop::new(),
+            events_loop: events_loop,
+            user_events: Arc::new(Mutex::new(VecDeque::new())),
+            pending_wakeup: Arc::new(AtomicBool::new(false)),
+            redraw_requests: Arc::new(Mutex::new(Vec::new())),
+            coalesce: false,
             _marker: ::std::marker::PhantomData,
         }
     }
@@ -217,47 +300,396 @@ impl EventsLoop {
         MonitorId { inner: self.events_loop.get_primary_monitor() }
     }
 
+    /// Enables or disables the coalescing of high-frequency events. Disabled by default.
+    ///
+    /// When enabled, the following events are merged with the previous event of the same kind,
+    /// for the same window and device, as long as no other event was received in between:
+    ///
+    /// - `WindowEvent::MouseMoved`, `WindowEvent::AxisMotion`, `WindowEvent::Resized` and
+    ///   `WindowEvent::Moved`, for which only the last value is kept.
+    /// - `DeviceEvent::Motion` on the same axis, whose values are summed.
+    ///
+    /// The other events, such as button and key events, are still delivered in order, after the
+    /// merged events that were received before them.
+    ///
+    /// The events are merged before reaching the callback, which saves calls to the callback but
+    /// not the processing of the events by the backend.
+    #[inline]
+    pub fn set_coalescing(&mut self, coalesce: bool) {
+        self.coalesce = coalesce;
+    }
+
     /// Fetches all the events that are pending, calls the callback function for each of them,
     /// and returns.
+    ///
+    /// If there is at least one event, they are delivered as a single batch: the first one is
+    /// preceded by `Event::NewEvents` and the last one is followed by `Event::EventsCleared`.
+    #[inline]
+    pub fn poll_events<F>(&mut self, mut callback: F)
+        where F: FnMut(Event<T>)
+    {
+        let mut callback = |event| {
+            callback(event);
+            ControlFlow::Continue
+        };
+        let mut batch = Batch::new(&self.user_events, &self.pending_wakeup, &self.redraw_requests,
+                                   self.coalesce);
+        self.events_loop.poll_events(|event| {
+            batch.forward(event, &mut callback);
+        });
+        batch.end(&mut callback);
+    }
+
+    /// Waits until at least one event is available or `timeout` has elapsed, then fetches all
+    /// the events that are pending, calls the callback function for each of them, and returns.
+    ///
+    /// The events are delivered as a single batch, like with `poll_events`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only X11, Wayland and Windows wait for the timeout. Other platforms behave like
+    ///   `poll_events`.
     #[inline]
-    pub fn poll_events<F>(&mut self, callback: F)
-        where F: FnMut(Event)
+    pub fn poll_events_timeout<F>(&mut self, timeout: Duration, mut callback: F)
+        where F: FnMut(Event<T>)
     {
-        self.events_loop.poll_events(callback)
+        let mut callback = |event| {
+            callback(event);
+            ControlFlow::Continue
+        };
+        let mut batch = Batch::new(&self.user_events, &self.pending_wakeup, &self.redraw_requests,
+                                   self.coalesce);
+        self.events_loop.poll_events_timeout(timeout, |event| {
+            batch.forward(event, &mut callback);
+        });
+        batch.end(&mut callback);
     }
 
     /// Calls `callback` every time an event is received. If no event is available, sleeps the
     /// current thread and waits for an event. If the callback returns `ControlFlow::Break` then
     /// `run_forever` will immediately return.
+    ///
+    /// See the documentation of `ControlFlow` for the other ways the callback can control how
+    /// the events loop waits.
+    ///
+    /// The events received after each wakeup are delivered as a batch, which starts with
+    /// `Event::NewEvents` and ends with `Event::EventsCleared`. The callback receives
+    /// `Event::LoopDestroyed` right before `run_forever` returns.
     #[inline]
-    pub fn run_forever<F>(&mut self, callback: F)
-        where F: FnMut(Event) -> ControlFlow
+    pub fn run_forever<F>(&mut self, mut callback: F)
+        where F: FnMut(Event<T>) -> ControlFlow
     {
-        self.events_loop.run_forever(callback)
+        let mut batch = Batch::new(&self.user_events, &self.pending_wakeup, &self.redraw_requests,
+                                   self.coalesce);
+        {
+            let batch = &mut batch;
+            let callback = &mut callback;
+            // The value returned by the user for the last event it received. Kept around so that
+            // a wakeup that doesn't produce any event doesn't change the way the events loop waits.
+            let mut control_flow = ControlFlow::Continue;
+            self.events_loop.run_forever(|event| {
+                if let Some(flow) = batch.forward(event, callback) {
+                    control_flow = flow;
+                }
+                control_flow
+            });
+        }
+        batch.end(&mut callback);
+        callback(Event::LoopDestroyed);
     }
 
     /// Creates an `EventsLoopProxy` that can be used to wake up the `EventsLoop` from another
     /// thread.
-    pub fn create_proxy(&self) -> EventsLoopProxy {
+    pub fn create_proxy(&self) -> EventsLoopProxy<T> {
         EventsLoopProxy {
             events_loop_proxy: self.events_loop.create_proxy(),
+            user_events: Arc::downgrade(&self.user_events),
+            pending_wakeup: Arc::downgrade(&self.pending_wakeup),
         }
     }
 }
 
-/// Used to wake up the `EventsLoop` from another thread.
-#[derive(Clone)]
-pub struct EventsLoopProxy {
+// Splits the events passed to the user callback into batches.
+//
+// A batch starts with `Event::NewEvents`, which is sent right before the first event, so that
+// wakeups that don't produce any event don't produce empty batches either. It ends with the
+// `RedrawRequested` events of the windows that need to be redrawn, followed by
+// `Event::EventsCleared`.
+struct Batch<'a, T: 'a> {
+    user_events: &'a Mutex<VecDeque<T>>,
+    pending_wakeup: &'a AtomicBool,
+    redraw_requests: &'a Mutex<Vec<WindowId>>,
+    started: bool,
+    // See `EventsLoop::set_coalescing`.
+    coalesce: bool,
+    // The events that may still be merged with the next ones, in the order they were received.
+    coalesced: Vec<Event<T>>,
+}
+
+impl<'a, T> Batch<'a, T> {
+    fn new(user_events: &'a Mutex<VecDeque<T>>, pending_wakeup: &'a AtomicBool,
+           redraw_requests: &'a Mutex<Vec<WindowId>>, coalesce: bool) -> Batch<'a, T>
+    {
+        Batch {
+            user_events: user_events,
+            pending_wakeup: pending_wakeup,
+            redraw_requests: redraw_requests,
+            started: false,
+            coalesce: coalesce,
+            coalesced: Vec::new(),
+        }
+    }
+
+    // Passes an event generated by the backend to the user callback.
+    //
+    // The backend generates an `Awakened` event whenever an `EventsLoopProxy` wakes it up. This
+    // event is replaced with the user events that are in the queue, followed by an `Awakened`
+    // event if `EventsLoopProxy::wakeup` was called.
+    //
+    // The backends generate `Event::EventsCleared` in `run_forever` whenever they are about to
+    // wait for new events. It ends the current batch, or is dropped if there is nothing to deliver.
+    //
+    // Returns the value returned by the callback, or `None` if the callback wasn't called. If the
+    // callback is called multiple times, `ControlFlow::Break` takes precedence.
+    fn forward<F>(&mut self, event: Event, callback: &mut F) -> Option<ControlFlow>
+        where F: FnMut(Event<T>) -> ControlFlow
+    {
+        match event {
+            Event::EventsCleared => self.end(callback),
+            Event::Awakened => {
+                // Take the whole queue at once, so that the lock isn't held while the callback
+                // runs.
+                let pending = mem::replace(&mut *self.user_events.lock().unwrap(), VecDeque::new());
+                let mut control_flow = None;
+                for user_event in pending {
+                    let flow = self.deliver(Event::UserEvent(user_event), callback);
+                    merge_control_flow(&mut control_flow, flow);
+                }
+                if self.pending_wakeup.swap(false, Ordering::Relaxed) {
+                    let flow = self.deliver(Event::Awakened, callback);
+                    merge_control_flow(&mut control_flow, flow);
+                }
+                control_flow
+            },
+            event => {
+                // The system asks for a redraw, which is merged with the ones requested by the
+                // user.
+                if let Event::WindowEvent { window_id, event: WindowEvent::Refresh } = event {
+                    request_redraw(self.redraw_requests, window_id);
+                }
+                event.map_nonuser_event().and_then(|event| self.deliver(event, callback))
+            },
+        }
+    }
+
+    // Passes `event` to the user callback, starting a new batch first if needed.
+    //
+    // If coalescing is enabled, the event may be kept to be merged with the next ones instead.
+    fn deliver<F>(&mut self, event: Event<T>, callback: &mut F) -> Option<ControlFlow>
+        where F: FnMut(Event<T>) -> ControlFlow
+    {
+        let mut control_flow = None;
+        if !self.started {
+            self.started = true;
+            control_flow = Some(callback(Event::NewEvents));
+        }
+
+        let event = if self.coalesce {
+            match coalesce(&mut self.coalesced, event) {
+                Some(event) => event,
+                None => return control_flow,
+            }
+        } else {
+            event
+        };
+
+        let flow = self.flush(callback);
+        merge_control_flow(&mut control_flow, flow);
+        merge_control_flow(&mut control_flow, Some(callback(event)));
+        control_flow
+    }
+
+    // Delivers the events that were kept to be merged.
+    fn flush<F>(&mut self, callback: &mut F) -> Option<ControlFlow>
+        where F: FnMut(Event<T>) -> ControlFlow
+    {
+        let mut control_flow = None;
+        for event in self.coalesced.drain(..) {
+            merge_control_flow(&mut control_flow, Some(callback(event)));
+        }
+        control_flow
+    }
+
+    // Delivers the pending redraw requests and ends the current batch, if there is one.
+    //
+    // Redraws requested by the callback from now on are delivered in the next batch.
+    fn end<F>(&mut self, callback: &mut F) -> Option<ControlFlow>
+        where F: FnMut(Event<T>) -> ControlFlow
+    {
+        let mut control_flow = self.flush(callback);
+
+        let redraw_requests = mem::replace(&mut *self.redraw_requests.lock().unwrap(), Vec::new());
+        let mut events: Vec<Event<T>> = redraw_requests.into_iter().map(|window_id| {
+            Event::WindowEvent {
+                window_id: window_id,
+                event: WindowEvent::RedrawRequested,
+            }
+        }).collect();
+        if !self.started {
+            if events.is_empty() {
+                return None;
+            }
+            events.insert(0, Event::NewEvents);
+        }
+        self.started = false;
+        events.push(Event::EventsCleared);
+
+        for event in events {
+            merge_control_flow(&mut control_flow, Some(callback(event)));
+        }
+        control_flow
+    }
+}
+
+// Combines the values returned by successive calls to the user callback. The last value is kept,
+// but `ControlFlow::Break` takes precedence.
+fn merge_control_flow(control_flow: &mut Option<ControlFlow>, flow: Option<ControlFlow>) {
+    if flow.is_some() && *control_flow != Some(ControlFlow::Break) {
+        *control_flow = flow;
+    }
+}
+
+// Merges `event` into the last event of `pending` if they match, or adds it to `pending`.
+//
+// Returns `event` back if it can't be coalesced, in which case `pending` must be delivered first.
+fn coalesce<T>(pending: &mut Vec<Event<T>>, event: Event<T>) -> Option<Event<T>> {
+    match event {
+        Event::WindowEvent { event: WindowEvent::MouseMoved { .. }, .. } |
+        Event::WindowEvent { event: WindowEvent::AxisMotion { .. }, .. } |
+        Event::WindowEvent { event: WindowEvent::Resized(..), .. } |
+        Event::WindowEvent { event: WindowEvent::Moved(..), .. } |
+        Event::DeviceEvent { event: DeviceEvent::Motion { .. }, .. } => (),
+        event => return Some(event),
+    }
+
+    // Merging into an earlier event would deliver `event` before the ones received after that
+    // event.
+    let merged = match pending.last_mut() {
+        Some(last) => merge_event(last, &event),
+        None => false,
+    };
+    if !merged {
+        pending.push(event);
+    }
+    None
+}
+
+// Merges `event` into `pending` if both are coalescable events of the same kind, for the same
+// window or device. Returns `false` if they can't be merged.
+fn merge_event<T>(pending: &mut Event<T>, event: &Event<T>) -> bool {
+    match (pending, event) {
+        (&mut Event::WindowEvent { window_id: pending_window_id, event: ref mut pending },
+         &Event::WindowEvent { window_id, ref event }) => {
+            if pending_window_id != window_id {
+                return false;
+            }
+            match (pending, event) {
+                (&mut WindowEvent::MouseMoved { device_id: pending_device_id, ref mut position },
+                 &WindowEvent::MouseMoved { device_id, position: new_position }) => {
+                    if pending_device_id != device_id {
+                        return false;
+                    }
+                    *position = new_position;
+                },
+                (&mut WindowEvent::AxisMotion { device_id: pending_device_id, axis: pending_axis, ref mut value },
+                 &WindowEvent::AxisMotion { device_id, axis, value: new_value }) => {
+                    if pending_device_id != device_id || pending_axis != axis {
+                        return false;
+                    }
+                    *value = new_value;
+                },
+                (&mut WindowEvent::Resized(ref mut size), &WindowEvent::Resized(new_size)) => {
+                    *size = new_size;
+                },
+                (&mut WindowEvent::Moved(ref mut position), &WindowEvent::Moved(new_position)) => {
+                    *position = new_position;
+                },
+                _ => return false,
+            }
+            true
+        },
+        (&mut Event::DeviceEvent { device_id: pending_device_id,
+                                   event: DeviceEvent::Motion { axis: pending_axis, ref mut value } },
+         &Event::DeviceEvent { device_id, event: DeviceEvent::Motion { axis, value: delta } }) => {
+            if pending_device_id != device_id || pending_axis != axis {
+                return false;
+            }
+            // Raw motion is relative, so the deltas add up.
+            *value += delta;
+            true
+        },
+        _ => false,
+    }
+}
+
+// Adds `window_id` to the windows that need to be redrawn, if it isn't there yet.
+fn request_redraw(redraw_requests: &Mutex<Vec<WindowId>>, window_id: WindowId) {
+    let mut redraw_requests = redraw_requests.lock().unwrap();
+    if !redraw_requests.contains(&window_id) {
+        redraw_requests.push(window_id);
+    }
+}
+
+/// Used to wake up the `EventsLoop` from another thread, and to send user events to it.
+pub struct EventsLoopProxy<T = ()> {
     events_loop_proxy: platform::EventsLoopProxy,
+    user_events: Weak<Mutex<VecDeque<T>>>,
+    pending_wakeup: Weak<AtomicBool>,
 }
 
-impl EventsLoopProxy {
+// Implemented manually because deriving would require `T: Clone`.
+impl<T> Clone for EventsLoopProxy<T> {
+    fn clone(&self) -> EventsLoopProxy<T> {
+        EventsLoopProxy {
+            events_loop_proxy: self.events_loop_proxy.clone(),
+            user_events: self.user_events.clone(),
+            pending_wakeup: self.pending_wakeup.clone(),
+        }
+    }
+}
+
+impl<T> EventsLoopProxy<T> {
     /// Wake up the `EventsLoop` from which this proxy was created.
     ///
-    /// This causes the `EventsLoop` to emit an `Awakened` event.
+    /// This causes the `EventsLoop` to emit an `Awakened` event. Multiple calls to `wakeup` that
+    /// happen before the `EventsLoop` had the chance to process them may be merged into a single
+    /// `Awakened` event.
     ///
     /// Returns an `Err` if the associated `EventsLoop` no longer exists.
     pub fn wakeup(&self) -> Result<(), EventsLoopClosed> {
+        match self.pending_wakeup.upgrade() {
+            Some(pending_wakeup) => pending_wakeup.store(true, Ordering::Relaxed),
+            None => return Err(EventsLoopClosed),
+        }
+        self.events_loop_proxy.wakeup()
+    }
+
+    /// Sends an event to the `EventsLoop` from which this proxy was created, and wakes it up.
+    ///
+    /// The event is received as an `Event::UserEvent`. Events are queued: every event sent with
+    /// this method is received exactly once, in the order they were sent.
+    ///
+    /// Returns an `Err` if the associated `EventsLoop` no longer exists.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Not implemented on Android, iOS and Emscripten, where waking up the events loop isn't
+    ///   supported.
+    pub fn send_event(&self, event: T) -> Result<(), EventsLoopClosed> {
+        match self.user_events.upgrade() {
+            Some(user_events) => user_events.lock().unwrap().push_back(event),
+            None => return Err(EventsLoopClosed),
+        }
         self.events_loop_proxy.wakeup()
     }
 }
@@ -279,6 +711,100 @@ impl std::error::Error for EventsLoopClosed {
     }
 }
 
+/// Error that can happen while creating an `EventsLoop`.
+#[derive(Debug, Clone)]
+pub enum EventsLoopCreationError {
+    /// The `WINIT_UNIX_BACKEND` environment variable contains an unknown value, which is given.
+    UnknownBackend(String),
+    /// None of the backends could be initialized.
+    ///
+    /// Contains the reason why each backend failed, in the order they were tried.
+    NoBackendAvailable(Vec<BackendError>),
+}
+
+impl std::fmt::Display for EventsLoopCreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            EventsLoopCreationError::UnknownBackend(ref value) => {
+                write!(f, "Unknown value `{}` for the WINIT_UNIX_BACKEND environment variable, \
+                           try one of `x11`, `wayland`, `headless`", value)
+            },
+            EventsLoopCreationError::NoBackendAvailable(ref errors) => {
+                try!(write!(f, "No backend is available"));
+                for err in errors {
+                    try!(write!(f, "; {}", err));
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl std::error::Error for EventsLoopCreationError {
+    fn description(&self) -> &str {
+        match *self {
+            EventsLoopCreationError::UnknownBackend(_) => "Unknown backend requested",
+            EventsLoopCreationError::NoBackendAvailable(_) => "No backend is available",
+        }
+    }
+}
+
+/// The reason why a backend couldn't be initialized.
+///
+/// Only Linux has backends that can fail to initialize. On other platforms, this enum has no
+/// variant.
+#[derive(Debug, Clone)]
+pub enum BackendError {
+    /// The X11 backend couldn't be initialized.
+    #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+    X11(platform::XNotSupported),
+    /// The Wayland backend couldn't be initialized.
+    #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+    Wayland(platform::WaylandNotSupported),
+    /// The headless backend couldn't be initialized.
+    #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+    Headless(platform::HeadlessNotSupported),
+}
+
+impl std::fmt::Display for BackendError {
+    // `f` is unused on the platforms where the enum has no variant.
+    #[allow(unused_variables)]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::X11(ref err) => write!(f, "x11: {}", err),
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::Wayland(ref err) => write!(f, "wayland: {}", err),
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::Headless(ref err) => write!(f, "headless: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {
+    fn description(&self) -> &str {
+        match *self {
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::X11(ref err) => std::error::Error::description(err),
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::Wayland(ref err) => std::error::Error::description(err),
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::Headless(ref err) => std::error::Error::description(err),
+        }
+    }
+
+    fn cause(&self) -> Option<&std::error::Error> {
+        match *self {
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::X11(ref err) => Some(err),
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::Wayland(ref err) => Some(err),
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+            BackendError::Headless(ref err) => Some(err),
+        }
+    }
+}
+
 /// Object that allows you to build windows.
 #[derive(Clone)]
 pub struct WindowBuilder {
@@ -395,26 +921,31 @@ pub enum CursorState {
 /// Attributes to use when creating a window.
 #[derive(Clone)]
 pub struct WindowAttributes {
-    /// The dimensions of the window. If this is `None`, some platform-specific dimensions will be
-    /// used.
+    /// The dimensions of the client area of the window. If this is `None`, some platform-specific
+    /// dimensions will be used.
     ///
     /// The default is `None`.
-    pub dimensions: Option<(u32, u32)>,
+    pub dimensions: Option<LogicalSize>,
 
     /// The minimum dimensions a window can be, If this is `None`, the window will have no minimum dimensions (aside from reserved).
     ///
     /// The default is `None`.
-    pub min_dimensions: Option<(u32, u32)>,
+    pub min_dimensions: Option<LogicalSize>,
 
     /// The maximum dimensions a window can be, If this is `None`, the maximum will have no maximum or will be set to the primary monitor's dimensions by the platform.
     ///
     /// The default is `None`.
-    pub max_dimensions: Option<(u32, u32)>,
+    pub max_dimensions: Option<LogicalSize>,
+
+    /// Whether the window can be resized by the user.
+    ///
+    /// The default is `true`.
+    pub resizable: bool,
 
     /// Whether the window should be set as fullscreen upon creation.
     ///
     /// The default is `None`.
-    pub fullscreen: Option<MonitorId>,
+    pub fullscreen: Option<Fullscreen>,
 
     /// The title of the window in the title bar.
     ///
@@ -442,6 +973,11 @@ pub struct WindowAttributes {
     /// The default is `true`.
     pub decorations: bool,
 
+    /// Whether the window should stay above the other windows.
+    ///
+    /// The default is `false`.
+    pub always_on_top: bool,
+
     /// [iOS only] Enable multitouch, see [UIView#multipleTouchEnabled]
     /// (https://developer.apple.com/library/ios/documentation/UIKit/Reference/UIView_Class/#//apple_ref/occ/instp/UIView/multipleTouchEnabled)
     pub multitouch: bool,
@@ -454,12 +990,14 @@ impl Default for WindowAttributes {
             dimensions: None,
             min_dimensions: None,
             max_dimensions: None,
+            resizable: true,
             title: "winit window".to_owned(),
             maximized: false,
             fullscreen: None,
             visible: true,
             transparent: false,
             decorations: true,
+            always_on_top: false,
             multitouch: false,
         }
     }
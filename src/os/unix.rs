@@ -1,14 +1,24 @@
 #![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
 
+use std::io;
+use std::os::unix::io::RawFd;
 use std::sync::Arc;
 use std::ptr;
 use libc;
+use ControlFlow;
+use ElementState;
+use Event;
 use EventsLoop;
+use Readiness;
+use Signal;
 use MonitorId;
+use MouseButton;
+use ScanCode;
 use Window;
 use platform::EventsLoop as LinuxEventsLoop;
 use platform::Window as LinuxWindow;
 use WindowBuilder;
+use dpi::{LogicalPosition, PhysicalPosition};
 use platform::x11::XConnection;
 use platform::x11::ffi::XVisualInfo;
 
@@ -16,16 +26,30 @@ use platform::x11::ffi::XVisualInfo;
 #[doc(hidden)]
 pub use platform::x11;
 
-pub use platform::XNotSupported;
+pub use platform::{HeadlessNotSupported, WaylandNotSupported, XNotSupported};
+pub use platform::headless::Injector as HeadlessInjector;
 
 /// Additional methods on `EventsLoop` that are specific to Linux.
 pub trait EventsLoopExt {
+    /// The type of the user events received by the `EventsLoop`.
+    type UserEvent;
+
     /// Builds a new `EventsLoop` that is forced to use X11.
     fn new_x11() -> Result<Self, XNotSupported>
         where Self: Sized;
 
     /// Builds a new `EventsLoop` that is forced to use Wayland.
-    fn new_wayland() -> Self
+    fn new_wayland() -> Result<Self, WaylandNotSupported>
+        where Self: Sized;
+
+    /// Builds a new `EventsLoop` that doesn't connect to any display server.
+    ///
+    /// Its windows only exist in memory, and it only receives the input pushed by the
+    /// `HeadlessInjector`s returned by `headless_injector`. It has two fake 1920x1080 monitors.
+    /// This is also what `WINIT_UNIX_BACKEND=headless` selects.
+    ///
+    /// Only fails if the process has run out of file descriptors.
+    fn new_headless() -> Result<Self, HeadlessNotSupported>
         where Self: Sized;
 
     /// True if the `EventsLoop` uses Wayland.
@@ -34,30 +58,102 @@ pub trait EventsLoopExt {
     /// True if the `EventsLoop` uses X11.
     fn is_x11(&self) -> bool;
 
+    /// True if the `EventsLoop` is headless.
+    fn is_headless(&self) -> bool;
+
+    /// Returns a handle that pushes synthetic input into the `EventsLoop`, or `None` if the
+    /// `EventsLoop` isn't headless.
+    fn headless_injector(&self) -> Option<HeadlessInjector>;
+
+    /// Simulates a press or release of the key with the given scancode, as reported by
+    /// `KeyboardInput::scancode`, through the XTest extension.
+    ///
+    /// Unlike the events of a `HeadlessInjector`, the simulated input goes through the X server,
+    /// which delivers it to the focused window. It is meant for testing against a server such as
+    /// Xvfb. Returns an error if the `EventsLoop` doesn't use X11 or if XTest isn't available.
+    fn xtest_keyboard_input(&self, scancode: ScanCode, state: ElementState) -> Result<(), ()>;
+
+    /// Simulates a press or release of a mouse button through the XTest extension.
+    ///
+    /// Returns an error if the `EventsLoop` doesn't use X11 or if XTest isn't available.
+    fn xtest_mouse_input(&self, state: ElementState, button: MouseButton) -> Result<(), ()>;
+
+    /// Simulates a motion of the mouse to `position`, relative to the top-left corner of the
+    /// screen the pointer is on, through the XTest extension. See also
+    /// `WindowExt::xtest_mouse_motion`.
+    ///
+    /// Returns an error if the `EventsLoop` doesn't use X11 or if XTest isn't available.
+    fn xtest_mouse_motion(&self, position: PhysicalPosition) -> Result<(), ()>;
+
+    /// Simulates a scroll of the given number of lines through the XTest extension, using the
+    /// same directions as `MouseScrollDelta::LineDelta`. Each line is a click of a wheel button.
+    ///
+    /// Returns an error if the `EventsLoop` doesn't use X11 or if XTest isn't available.
+    fn xtest_mouse_wheel(&self, horizontal: i32, vertical: i32) -> Result<(), ()>;
+
+    /// Returns the file descriptor of the connection to the X or Wayland server. For a headless
+    /// `EventsLoop`, this is a pipe that becomes readable when an event is injected.
+    ///
+    /// This file descriptor becomes readable when the server sends data, which makes it possible
+    /// to integrate the `EventsLoop` in an external event loop (for example one based on `epoll`
+    /// or `mio`) instead of calling `run_forever`. Wait for it to be readable, then call
+    /// `dispatch_ready`. You must not read from or write to this file descriptor yourself.
+    fn get_display_fd(&self) -> RawFd;
+
+    /// Processes the data that has already been received from the X or Wayland server, calls
+    /// `callback` for each resulting event, and returns. Never blocks.
+    ///
+    /// This method also sends the pending requests to the server before returning. Note that the
+    /// connection may have queued events without the file descriptor being readable (for example
+    /// while waiting for the reply to a request made through a `Window`), so this method should
+    /// also be called before waiting again after using a `Window`.
+    ///
+    /// The events are delivered as a single batch, like with `EventsLoop::poll_events`.
+    fn dispatch_ready<F>(&mut self, callback: F)
+        where F: FnMut(Event<Self::UserEvent>);
+
+    /// Starts watching `fd` in addition to the connection to the X or Wayland server.
+    ///
+    /// Whenever `fd` is ready for one of the operations of `interest`, the `EventsLoop` wakes up
+    /// and generates an `Event::FdReady` containing `token`. Registering a file descriptor that is
+    /// already registered replaces its token and interest.
+    ///
+    /// The file descriptor must be unregistered with `unregister_fd` before being closed.
+    fn register_fd(&mut self, fd: RawFd, token: usize, interest: Readiness);
+
+    /// Stops watching a file descriptor registered with `register_fd`.
+    ///
+    /// Does nothing if the file descriptor isn't registered.
+    fn unregister_fd(&mut self, fd: RawFd);
+
+    /// Installs handlers for `signals`, so that receiving them wakes up the `EventsLoop` and
+    /// generates an `Event::Signal`.
+    ///
+    /// Signal handlers are global to the process: if multiple `EventsLoop`s watch signals, only
+    /// the last one to call this method receives them. The previous handlers are restored when the
+    /// `EventsLoop` is destroyed.
+    fn watch_signals(&mut self, signals: &[Signal]) -> io::Result<()>;
+
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>>;
 }
 
-impl EventsLoopExt for EventsLoop {
+impl<T> EventsLoopExt for EventsLoop<T> {
+    type UserEvent = T;
+
     #[inline]
     fn new_x11() -> Result<Self, XNotSupported> {
-        LinuxEventsLoop::new_x11().map(|ev|
-            EventsLoop {
-                events_loop: ev,
-                _marker: ::std::marker::PhantomData,
-            }
-        )
+        LinuxEventsLoop::new_x11().map(EventsLoop::from_platform)
     }
 
     #[inline]
-    fn new_wayland() -> Self {
-        EventsLoop {
-            events_loop: match LinuxEventsLoop::new_wayland() {
-                Ok(e) => e,
-                Err(_) => panic!()      // TODO: propagate
-            },
-            _marker: ::std::marker::PhantomData,
-        }
+    fn new_wayland() -> Result<Self, WaylandNotSupported> {
+        LinuxEventsLoop::new_wayland().map(EventsLoop::from_platform)
+    }
+
+    #[inline]
+    fn new_headless() -> Result<Self, HeadlessNotSupported> {
+        LinuxEventsLoop::new_headless().map(EventsLoop::from_platform)
     }
 
     #[inline]
@@ -67,7 +163,92 @@ impl EventsLoopExt for EventsLoop {
 
     #[inline]
     fn is_x11(&self) -> bool {
-        !self.events_loop.is_wayland()
+        self.events_loop.is_x11()
+    }
+
+    #[inline]
+    fn is_headless(&self) -> bool {
+        self.events_loop.is_headless()
+    }
+
+    #[inline]
+    fn headless_injector(&self) -> Option<HeadlessInjector> {
+        self.events_loop.headless_injector()
+    }
+
+    fn xtest_keyboard_input(&self, scancode: ScanCode, state: ElementState) -> Result<(), ()> {
+        let xconn = try!(self.events_loop.x_connection().ok_or(()));
+        // Scancodes are X keycodes shifted by 8.
+        xconn.fake_key(scancode + 8, state == ElementState::Pressed)
+    }
+
+    fn xtest_mouse_input(&self, state: ElementState, button: MouseButton) -> Result<(), ()> {
+        let xconn = try!(self.events_loop.x_connection().ok_or(()));
+        let button = match button {
+            MouseButton::Left => x11::ffi::Button1,
+            MouseButton::Middle => x11::ffi::Button2,
+            MouseButton::Right => x11::ffi::Button3,
+            MouseButton::Other(button) => button as libc::c_uint,
+        };
+        xconn.fake_button(button, state == ElementState::Pressed)
+    }
+
+    #[inline]
+    fn xtest_mouse_motion(&self, position: PhysicalPosition) -> Result<(), ()> {
+        let xconn = try!(self.events_loop.x_connection().ok_or(()));
+        let (x, y) = position.into();
+        xconn.fake_motion(-1, x, y)
+    }
+
+    fn xtest_mouse_wheel(&self, horizontal: i32, vertical: i32) -> Result<(), ()> {
+        let xconn = try!(self.events_loop.x_connection().ok_or(()));
+        // Buttons 4 and 5 scroll up and down, 6 and 7 scroll left and right.
+        let clicks = [
+            (if vertical > 0 { 4 } else { 5 }, vertical.abs()),
+            (if horizontal > 0 { 7 } else { 6 }, horizontal.abs()),
+        ];
+        for &(button, count) in &clicks {
+            for _ in 0..count {
+                try!(xconn.fake_button(button, true));
+                try!(xconn.fake_button(button, false));
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get_display_fd(&self) -> RawFd {
+        self.events_loop.get_display_fd()
+    }
+
+    fn dispatch_ready<F>(&mut self, mut callback: F)
+        where F: FnMut(Event<T>)
+    {
+        let mut callback = |event| {
+            callback(event);
+            ControlFlow::Continue
+        };
+        let mut batch = ::Batch::new(&self.user_events, &self.pending_wakeup, &self.redraw_requests,
+                                     self.coalesce);
+        self.events_loop.dispatch_ready(|event| {
+            batch.forward(event, &mut callback);
+        });
+        batch.end(&mut callback);
+    }
+
+    #[inline]
+    fn register_fd(&mut self, fd: RawFd, token: usize, interest: Readiness) {
+        self.events_loop.event_sources().register_fd(fd, token, interest)
+    }
+
+    #[inline]
+    fn unregister_fd(&mut self, fd: RawFd) {
+        self.events_loop.event_sources().unregister_fd(fd)
+    }
+
+    #[inline]
+    fn watch_signals(&mut self, signals: &[Signal]) -> io::Result<()> {
+        self.events_loop.event_sources().watch_signals(signals)
     }
 
     #[inline]
@@ -127,8 +308,34 @@ pub trait WindowExt {
     ///
     /// Once it starts returning `true`, it can never return `false` again.
     ///
-    /// If the window is X11-based, this will just always return `true`.
+    /// If the window is X11-based or headless, this will just always return `true`.
     fn is_ready(&self) -> bool;
+
+    /// Simulates a motion of the mouse to `position`, relative to the top-left corner of the
+    /// window, through the XTest extension.
+    ///
+    /// Returns an error if the window doesn't use X11 or if XTest isn't available.
+    fn xtest_mouse_motion(&self, position: LogicalPosition) -> Result<(), ()>;
+
+    /// Sets whether the window appears on all the virtual desktops.
+    ///
+    /// Only has an effect on X11.
+    fn set_sticky(&self, sticky: bool);
+
+    /// Sets whether the window is left out of the taskbar.
+    ///
+    /// Only has an effect on X11.
+    fn set_skip_taskbar(&self, skip_taskbar: bool);
+
+    /// Sets whether the window is left out of the pager, which shows the virtual desktops.
+    ///
+    /// Only has an effect on X11.
+    fn set_skip_pager(&self, skip_pager: bool);
+
+    /// Moves the window to the virtual desktop of index `desktop`, starting at 0.
+    ///
+    /// Only has an effect on X11.
+    fn set_desktop(&self, desktop: u32);
 }
 
 impl WindowExt for Window {
@@ -197,7 +404,43 @@ impl WindowExt for Window {
     fn is_ready(&self) -> bool {
         match self.window {
             LinuxWindow::Wayland(ref w) => w.is_ready(),
-            LinuxWindow::X(_) => true
+            _ => true
+        }
+    }
+
+    #[inline]
+    fn xtest_mouse_motion(&self, position: LogicalPosition) -> Result<(), ()> {
+        match self.window {
+            LinuxWindow::X(ref w) => w.fake_motion(position),
+            _ => Err(())
+        }
+    }
+
+    #[inline]
+    fn set_sticky(&self, sticky: bool) {
+        if let LinuxWindow::X(ref w) = self.window {
+            w.set_sticky(sticky);
+        }
+    }
+
+    #[inline]
+    fn set_skip_taskbar(&self, skip_taskbar: bool) {
+        if let LinuxWindow::X(ref w) = self.window {
+            w.set_skip_taskbar(skip_taskbar);
+        }
+    }
+
+    #[inline]
+    fn set_skip_pager(&self, skip_pager: bool) {
+        if let LinuxWindow::X(ref w) = self.window {
+            w.set_skip_pager(skip_pager);
+        }
+    }
+
+    #[inline]
+    fn set_desktop(&self, desktop: u32) {
+        if let LinuxWindow::X(ref w) = self.window {
+            w.set_desktop(desktop);
         }
     }
 }
@@ -206,6 +449,16 @@ impl WindowExt for Window {
 pub trait WindowBuilderExt {
     fn with_x11_visual<T>(self, visual_infos: *const T) -> WindowBuilder;
     fn with_x11_screen(self, screen_id: i32) -> WindowBuilder;
+
+    /// Makes the window appear on all the virtual desktops. Only has an effect on X11.
+    fn with_sticky(self, sticky: bool) -> WindowBuilder;
+
+    /// Leaves the window out of the taskbar. Only has an effect on X11.
+    fn with_skip_taskbar(self, skip_taskbar: bool) -> WindowBuilder;
+
+    /// Leaves the window out of the pager, which shows the virtual desktops. Only has an effect
+    /// on X11.
+    fn with_skip_pager(self, skip_pager: bool) -> WindowBuilder;
 }
 
 impl WindowBuilderExt for WindowBuilder {
@@ -222,6 +475,24 @@ impl WindowBuilderExt for WindowBuilder {
         self.platform_specific.screen_id = Some(screen_id);
         self
     }
+
+    #[inline]
+    fn with_sticky(mut self, sticky: bool) -> WindowBuilder {
+        self.platform_specific.sticky = sticky;
+        self
+    }
+
+    #[inline]
+    fn with_skip_taskbar(mut self, skip_taskbar: bool) -> WindowBuilder {
+        self.platform_specific.skip_taskbar = skip_taskbar;
+        self
+    }
+
+    #[inline]
+    fn with_skip_pager(mut self, skip_pager: bool) -> WindowBuilder {
+        self.platform_specific.skip_pager = skip_pager;
+        self
+    }
 }
 
 /// Additional methods on `MonitorId` that are specific to Linux.
@@ -10,6 +10,7 @@ use CreationError::OsError;
 use WindowId as RootWindowId;
 use events::{Touch, TouchPhase};
 use window::MonitorId as RootMonitorId;
+use dpi::{LogicalPosition, LogicalSize};
 
 use std::collections::VecDeque;
 
@@ -32,6 +33,10 @@ impl EventsLoop {
         }
     }
 
+    pub fn try_new() -> Result<EventsLoop, ::EventsLoopCreationError> {
+        Ok(EventsLoop::new())
+    }
+
     #[inline]
     pub fn get_available_monitors(&self) -> VecDeque<MonitorId> {
         let mut rb = VecDeque::new();
@@ -61,7 +66,7 @@ impl EventsLoop {
                                 android_glue::MotionAction::Up => TouchPhase::Ended,
                                 android_glue::MotionAction::Cancel => TouchPhase::Cancelled,
                             },
-                            location: (motion.x as f64, motion.y as f64),
+                            location: LogicalPosition::new(motion.x as f64, motion.y as f64),
                             id: motion.pointer_id as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -82,11 +87,11 @@ impl EventsLoop {
                     if native_window.is_null() {
                         None
                     } else {
-                        let w = unsafe { ffi::ANativeWindow_getWidth(native_window as *const _) } as u32;
-                        let h = unsafe { ffi::ANativeWindow_getHeight(native_window as *const _) } as u32;
+                        let w = unsafe { ffi::ANativeWindow_getWidth(native_window as *const _) } as f64;
+                        let h = unsafe { ffi::ANativeWindow_getHeight(native_window as *const _) } as f64;
                         Some(Event::WindowEvent {
                             window_id: RootWindowId(WindowId),
-                            event: WindowEvent::Resized(w, h),
+                            event: WindowEvent::Resized(LogicalSize::new(w, h)),
                         })
                     }
                 },
@@ -108,6 +113,12 @@ impl EventsLoop {
         };
     }
 
+    pub fn poll_events_timeout<F>(&mut self, _timeout: ::std::time::Duration, callback: F)
+        where F: FnMut(::Event)
+    {
+        self.poll_events(callback)
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(::Event) -> ::ControlFlow,
     {
@@ -119,6 +130,9 @@ impl EventsLoop {
                     control_flow = ::ControlFlow::Break;
                 }
             });
+            if let ::ControlFlow::Break = callback(::Event::EventsCleared) {
+                control_flow = ::ControlFlow::Break;
+            }
             if let ::ControlFlow::Break = control_flow {
                 break;
             }
@@ -171,9 +185,76 @@ impl MonitorId {
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         1.0
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<::VideoMode> {
+        // TODO: enumerate the video modes
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<::GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &::GammaRamp) -> Result<(), ()> {
+        Err(())
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> ::MonitorTransform {
+        ::MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> ::SubpixelOrder {
+        ::SubpixelOrder::Unknown
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        None
+    }
+
+    // What `PartialEq` and `Hash` of the root `MonitorId` are based on.
+    #[inline]
+    pub fn get_identity(&self) -> String {
+        self.get_stable_identifier()
+    }
+
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        "Primary".to_owned()
+    }
 }
 
 #[derive(Clone, Default)]
@@ -220,33 +301,45 @@ impl Window {
     }
 
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         None
     }
 
     #[inline]
-    pub fn set_position(&self, _x: i32, _y: i32) {
+    pub fn set_position(&self, _position: LogicalPosition) {
     }
 
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         if self.native_window.is_null() {
             None
         } else {
-            Some((
-                unsafe { ffi::ANativeWindow_getWidth(self.native_window as *const _) } as u32,
-                unsafe { ffi::ANativeWindow_getHeight(self.native_window as *const _) } as u32
+            Some(LogicalSize::new(
+                unsafe { ffi::ANativeWindow_getWidth(self.native_window as *const _) } as f64,
+                unsafe { ffi::ANativeWindow_getHeight(self.native_window as *const _) } as f64
             ))
         }
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         self.get_inner_size()
     }
 
     #[inline]
-    pub fn set_inner_size(&self, _x: u32, _y: u32) {
+    pub fn set_inner_size(&self, _size: LogicalSize) {
+    }
+
+    #[inline]
+    pub fn set_min_dimensions(&self, _dimensions: Option<LogicalSize>) {
+    }
+
+    #[inline]
+    pub fn set_max_dimensions(&self, _dimensions: Option<LogicalSize>) {
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, _resizable: bool) {
     }
 
     #[inline]
@@ -269,12 +362,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
+    pub fn hidpi_factor(&self) -> f64 {
         1.0
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, _x: i32, _y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ()> {
         Ok(())
     }
 
@@ -284,10 +377,23 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
+    pub fn set_minimized(&self, _minimized: bool) {
+    }
+
+    #[inline]
+    pub fn set_always_on_top(&self, _always_on_top: bool) {
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<::Fullscreen>) {
         // Android has single screen maximized apps so nothing to do
     }
 
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        ::WindowState { focused: true, visible: true, ..Default::default() }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         RootMonitorId{inner: MonitorId}
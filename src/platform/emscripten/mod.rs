@@ -10,6 +10,8 @@ use std::sync::{Mutex, Arc, Weak};
 use std::cell::RefCell;
 use std::collections::VecDeque;
 
+use dpi::{LogicalPosition, LogicalSize};
+
 const DOCUMENT_NAME: &'static str = "#document\0";
 
 #[derive(Clone, Default)]
@@ -44,9 +46,76 @@ impl MonitorId {
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         1.0
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<::VideoMode> {
+        // TODO: enumerate the video modes
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<::GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &::GammaRamp) -> Result<(), ()> {
+        Err(())
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> ::MonitorTransform {
+        ::MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> ::SubpixelOrder {
+        ::SubpixelOrder::Unknown
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        None
+    }
+
+    // What `PartialEq` and `Hash` of the root `MonitorId` are based on.
+    #[inline]
+    pub fn get_identity(&self) -> String {
+        self.get_stable_identifier()
+    }
+
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        "Canvas".to_owned()
+    }
 }
 
 // Used to assign a callback to emscripten main loop
@@ -73,7 +142,9 @@ pub struct EventsLoopProxy;
 
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), ::EventsLoopClosed> {
-        unimplemented!()
+        // The main loop callback runs on every frame of the browser, which delivers the pending
+        // wakeup, so there is nothing to interrupt.
+        Ok(())
     }
 }
 
@@ -90,12 +161,16 @@ impl EventsLoop {
         }
     }
 
+    pub fn try_new() -> Result<EventsLoop, ::EventsLoopCreationError> {
+        Ok(EventsLoop::new())
+    }
+
     pub fn interrupt(&self) {
         self.interrupted.store(true, Ordering::Relaxed);
     }
 
     pub fn create_proxy(&self) -> EventsLoopProxy {
-        unimplemented!()
+        EventsLoopProxy
     }
 
     #[inline]
@@ -121,6 +196,12 @@ impl EventsLoop {
         }
     }
 
+    pub fn poll_events_timeout<F>(&self, _timeout: ::std::time::Duration, callback: F)
+        where F: FnMut(::Event)
+    {
+        self.poll_events(callback)
+    }
+
     pub fn run_forever<F>(&self, mut callback: F)
         where F: FnMut(::Event) -> ::ControlFlow
     {
@@ -130,6 +211,7 @@ impl EventsLoop {
 
         set_main_loop_callback(|| {
             self.poll_events(|e| { callback(e); });
+            callback(::Event::EventsCleared);
             ::std::thread::sleep(::std::time::Duration::from_millis(5));
             if self.interrupted.load(Ordering::Relaxed) {
                 unsafe { ffi::emscripten_cancel_main_loop(); }
@@ -277,8 +359,8 @@ impl Window {
                 em_try(ffi::emscripten_set_fullscreenchange_callback(ptr::null(), 0 as *mut c_void, ffi::EM_FALSE, Some(fullscreen_callback)))
                     .map_err(|e| ::CreationError::OsError(e))?;
             }
-        } else if let Some((w, h)) = attribs.dimensions {
-            window.set_inner_size(w, h);
+        } else if let Some(size) = attribs.dimensions {
+            window.set_inner_size(size);
         }
 
         *events_loop.window.lock().unwrap() = Some(window.window.clone());
@@ -295,15 +377,15 @@ impl Window {
     }
 
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
-        Some((0, 0))
+    pub fn get_position(&self) -> Option<LogicalPosition> {
+        Some(LogicalPosition::new(0.0, 0.0))
     }
 
     #[inline]
-    pub fn set_position(&self, _: i32, _: i32) {
+    pub fn set_position(&self, _: LogicalPosition) {
     }
 
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         unsafe {
             use std::{mem, ptr};
             let mut width = mem::uninitialized();
@@ -314,25 +396,37 @@ impl Window {
             {
                 None
             } else {
-                Some((width as u32, height as u32))
+                Some(LogicalSize::new(width as f64, height as f64))
             }
         }
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         self.get_inner_size()
     }
 
     #[inline]
-    pub fn set_inner_size(&self, width: u32, height: u32) {
+    pub fn set_inner_size(&self, size: LogicalSize) {
         unsafe {
             use std::ptr;
-            ffi::emscripten_set_element_css_size(ptr::null(), width as c_double, height
+            ffi::emscripten_set_element_css_size(ptr::null(), size.width as c_double, size.height
                 as c_double);
         }
     }
 
+    #[inline]
+    pub fn set_min_dimensions(&self, _dimensions: Option<LogicalSize>) {
+    }
+
+    #[inline]
+    pub fn set_max_dimensions(&self, _dimensions: Option<LogicalSize>) {
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, _resizable: bool) {
+    }
+
     #[inline]
     pub fn show(&self) {}
     #[inline]
@@ -389,12 +483,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
-        unsafe { ffi::emscripten_get_device_pixel_ratio() as f32 }
+    pub fn hidpi_factor(&self) -> f64 {
+        unsafe { ffi::emscripten_get_device_pixel_ratio() as f64 }
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, _x: i32, _y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ()> {
         Err(())
     }
 
@@ -404,10 +498,23 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<::MonitorId>) {
+    pub fn set_minimized(&self, _minimized: bool) {
+    }
+
+    #[inline]
+    pub fn set_always_on_top(&self, _always_on_top: bool) {
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<::Fullscreen>) {
         // iOS has single screen maximized apps so nothing to do
     }
 
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        ::WindowState { focused: true, visible: true, ..Default::default() }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> ::MonitorId {
         ::MonitorId{inner: MonitorId}
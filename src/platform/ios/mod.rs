@@ -76,6 +76,7 @@ use WindowEvent;
 use Event;
 use events::{ Touch, TouchPhase };
 use window::MonitorId as RootMonitorId;
+use dpi::{LogicalPosition, LogicalSize};
 
 mod ffi;
 use self::ffi::{
@@ -111,14 +112,14 @@ struct DelegateState {
     events_queue: VecDeque<Event>,
     window: id,
     controller: id,
-    size: (u32,u32),
-    scale: f32
+    size: LogicalSize,
+    scale: f64
 }
 
 
 impl DelegateState {
     #[inline]
-    fn new(window: id, controller:id, size: (u32,u32), scale: f32) -> DelegateState {
+    fn new(window: id, controller:id, size: LogicalSize, scale: f64) -> DelegateState {
         DelegateState {
             events_queue: VecDeque::new(),
             window: window,
@@ -147,9 +148,76 @@ impl MonitorId {
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         1.0
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<::VideoMode> {
+        // TODO: enumerate the video modes
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<::GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &::GammaRamp) -> Result<(), ()> {
+        Err(())
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> ::MonitorTransform {
+        ::MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> ::SubpixelOrder {
+        ::SubpixelOrder::Unknown
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        None
+    }
+
+    // What `PartialEq` and `Hash` of the root `MonitorId` are based on.
+    #[inline]
+    pub fn get_identity(&self) -> String {
+        self.get_stable_identifier()
+    }
+
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        "Primary".to_owned()
+    }
 }
 
 pub struct EventsLoop {
@@ -183,6 +251,10 @@ impl EventsLoop {
         panic!("Couldn't create UIApplication")
     }
 
+    pub fn try_new() -> Result<EventsLoop, ::EventsLoopCreationError> {
+        Ok(EventsLoop::new())
+    }
+
     #[inline]
     pub fn get_available_monitors(&self) -> VecDeque<MonitorId> {
         let mut rb = VecDeque::new();
@@ -224,6 +296,12 @@ impl EventsLoop {
         }
     }
 
+    pub fn poll_events_timeout<F>(&mut self, _timeout: ::std::time::Duration, callback: F)
+        where F: FnMut(::Event)
+    {
+        self.poll_events(callback)
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(::Event) -> ::ControlFlow,
     {
@@ -235,6 +313,9 @@ impl EventsLoop {
                     control_flow = ::ControlFlow::Break;
                 }
             });
+            if let ::ControlFlow::Break = callback(::Event::EventsCleared) {
+                control_flow = ::ControlFlow::Break;
+            }
             if let ::ControlFlow::Break = control_flow {
                 break;
             }
@@ -249,7 +330,9 @@ impl EventsLoop {
 
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), ::EventsLoopClosed> {
-        unimplemented!()
+        // `run_forever` never blocks for long, it polls the run loop, so the pending wakeup is
+        // delivered without interrupting it.
+        Ok(())
     }
 }
 
@@ -284,26 +367,38 @@ impl Window {
     }
 
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         None
     }
 
     #[inline]
-    pub fn set_position(&self, _x: i32, _y: i32) {
+    pub fn set_position(&self, _position: LogicalPosition) {
     }
 
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         unsafe { Some((&*self.delegate_state).size) }
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         self.get_inner_size()
     }
 
     #[inline]
-    pub fn set_inner_size(&self, _x: u32, _y: u32) {
+    pub fn set_inner_size(&self, _size: LogicalSize) {
+    }
+
+    #[inline]
+    pub fn set_min_dimensions(&self, _dimensions: Option<LogicalSize>) {
+    }
+
+    #[inline]
+    pub fn set_max_dimensions(&self, _dimensions: Option<LogicalSize>) {
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, _resizable: bool) {
     }
 
     #[inline]
@@ -330,12 +425,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
+    pub fn hidpi_factor(&self) -> f64 {
         unsafe { (&*self.delegate_state) }.scale
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, _x: i32, _y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ()> {
         unimplemented!();
     }
 
@@ -350,10 +445,23 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
+    pub fn set_minimized(&self, _minimized: bool) {
+    }
+
+    #[inline]
+    pub fn set_always_on_top(&self, _always_on_top: bool) {
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<::Fullscreen>) {
         // iOS has single screen maximized apps so nothing to do
     }
 
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        ::WindowState { focused: true, visible: true, ..Default::default() }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         RootMonitorId{inner: MonitorId}
@@ -375,7 +483,7 @@ fn create_delegate_class() {
             let window: id = msg_send![Class::get("UIWindow").unwrap(), alloc];
             let window: id = msg_send![window, initWithFrame:bounds.clone()];
 
-            let size = (bounds.size.width as u32, bounds.size.height as u32);
+            let size = LogicalSize::new(bounds.size.width as f64, bounds.size.height as f64);
 
             let view_controller: id = msg_send![Class::get("MainViewController").unwrap(), alloc];
             let view_controller: id = msg_send![view_controller, init];
@@ -383,7 +491,7 @@ fn create_delegate_class() {
             let _: () = msg_send![window, setRootViewController:view_controller];
             let _: () = msg_send![window, makeKeyAndVisible];
 
-            let state = Box::new(DelegateState::new(window, view_controller, size, scale as f32));
+            let state = Box::new(DelegateState::new(window, view_controller, size, scale as f64));
             let state_ptr: *mut DelegateState = mem::transmute(state);
             this.set_ivar("glutinState", state_ptr as *mut c_void);
 
@@ -470,7 +578,7 @@ fn create_delegate_class() {
                     event: WindowEvent::Touch(Touch {
                         device_id: DEVICE_ID,
                         id: touch_id,
-                        location: (location.x as f64, location.y as f64),
+                        location: LogicalPosition::new(location.x as f64, location.y as f64),
                         phase: match phase {
                             0 => TouchPhase::Started,
                             1 => TouchPhase::Moved,
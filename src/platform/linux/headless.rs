@@ -0,0 +1,855 @@
+#![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+
+//! A backend that doesn't connect to any display server.
+//!
+//! Windows only exist in memory and input never comes from the user: it is pushed into the events
+//! loop with an `Injector`. This is meant for running tests of applications on machines that have
+//! neither X nor Wayland.
+
+use std::{fmt, mem};
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::os::unix::io::RawFd;
+use std::sync::{Arc, Mutex, Weak};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use libc;
+
+use {ControlFlow, CreationError, CursorState, ElementState, Event, EventsLoopClosed, KeyboardInput};
+use {ModifiersState, MouseButton, MouseCursor, MouseScrollDelta, ScanCode, Touch, TouchPhase};
+use {VirtualKeyCode, WindowAttributes, WindowEvent};
+use dpi::{LogicalPosition, LogicalSize};
+use platform::poll::{self, EventSources};
+use window::MonitorId as RootMonitorId;
+use window::{Fullscreen, MonitorTransform, SubpixelOrder, VideoMode};
+
+// The monitors of every headless events loop: two 1920x1080 screens side by side, the first one
+// being the primary monitor. Their DPI factor is 1, so logical and physical pixels are the same.
+const MONITORS: [((i32, i32), (u32, u32)); 2] = [
+    ((0, 0), (1920, 1080)),
+    ((1920, 0), (1920, 1080)),
+];
+
+// The state shared between the events loop, its windows, its proxies and its injectors.
+struct Shared {
+    // The events waiting to be delivered by the events loop.
+    queue: Mutex<VecDeque<Event>>,
+    windows: Mutex<HashMap<WindowId, WindowState>>,
+    next_window_id: AtomicUsize,
+    pending_wakeup: AtomicBool,
+    // A self-pipe. A byte is written to it whenever an event is queued or a wakeup is requested,
+    // so that the events loop can wait on the read end like on the connection of a display server.
+    read: RawFd,
+    write: RawFd,
+}
+
+impl Shared {
+    fn push(&self, event: Event) {
+        self.queue.lock().unwrap().push_back(event);
+        self.notify();
+    }
+
+    #[inline]
+    fn push_window_event(&self, window: WindowId, event: WindowEvent) {
+        self.push(Event::WindowEvent {
+            window_id: ::WindowId(super::WindowId::Headless(window)),
+            event: event,
+        });
+    }
+
+    fn notify(&self) {
+        // If the pipe is full the byte is dropped, but the read end is readable anyway.
+        let byte = 0u8;
+        unsafe { libc::write(self.write, &byte as *const u8 as *const libc::c_void, 1) };
+    }
+
+    fn drain_pipe(&self) {
+        let mut buffer = [0u8; 64];
+        loop {
+            let count = unsafe {
+                libc::read(self.read, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+            };
+            if count <= 0 {
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.read);
+            libc::close(self.write);
+        }
+    }
+}
+
+struct WindowState {
+    title: String,
+    position: LogicalPosition,
+    size: LogicalSize,
+    min_size: Option<LogicalSize>,
+    max_size: Option<LogicalSize>,
+    resizable: bool,
+    visible: bool,
+    maximized: bool,
+    minimized: bool,
+    focused: bool,
+    always_on_top: bool,
+    // The monitor the window is fullscreen on, and the position and size to restore when it
+    // leaves fullscreen.
+    fullscreen: Option<(MonitorId, LogicalPosition, LogicalSize)>,
+}
+
+impl WindowState {
+    fn clamp_size(&self, size: LogicalSize) -> LogicalSize {
+        let mut size = size;
+        if let Some(max) = self.max_size {
+            size.width = size.width.min(max.width);
+            size.height = size.height.min(max.height);
+        }
+        if let Some(min) = self.min_size {
+            size.width = size.width.max(min.width);
+            size.height = size.height.max(min.height);
+        }
+        size
+    }
+
+    fn root_state(&self) -> ::WindowState {
+        ::WindowState {
+            maximized: self.maximized,
+            minimized: self.minimized,
+            fullscreen: self.fullscreen.is_some(),
+            focused: self.focused,
+            visible: self.visible,
+        }
+    }
+}
+
+pub struct EventsLoop {
+    shared: Arc<Shared>,
+    sources: EventSources,
+}
+
+#[derive(Clone)]
+pub struct EventsLoopProxy {
+    shared: Weak<Shared>,
+}
+
+/// Error that can happen while creating a headless `EventsLoop`.
+#[derive(Clone, Debug)]
+pub enum HeadlessNotSupported {
+    /// The pipe used to wake up the events loop couldn't be created, for the given reason.
+    PipeCreationFailed(String),
+}
+
+impl Error for HeadlessNotSupported {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            HeadlessNotSupported::PipeCreationFailed(_) => "Failed to create the pipe of the events loop",
+        }
+    }
+}
+
+impl fmt::Display for HeadlessNotSupported {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            HeadlessNotSupported::PipeCreationFailed(ref reason) => {
+                write!(formatter, "{}: {}", self.description(), reason)
+            },
+        }
+    }
+}
+
+impl EventsLoop {
+    pub fn new() -> Result<EventsLoop, HeadlessNotSupported> {
+        let (read, write) = try!(poll::nonblocking_pipe().map_err(|err| {
+            HeadlessNotSupported::PipeCreationFailed(err.to_string())
+        }));
+
+        Ok(EventsLoop {
+            shared: Arc::new(Shared {
+                queue: Mutex::new(VecDeque::new()),
+                windows: Mutex::new(HashMap::new()),
+                next_window_id: AtomicUsize::new(0),
+                pending_wakeup: AtomicBool::new(false),
+                read: read,
+                write: write,
+            }),
+            sources: EventSources::new(),
+        })
+    }
+
+    pub fn create_proxy(&self) -> EventsLoopProxy {
+        EventsLoopProxy {
+            shared: Arc::downgrade(&self.shared),
+        }
+    }
+
+    pub fn create_injector(&self) -> Injector {
+        Injector {
+            shared: Arc::downgrade(&self.shared),
+        }
+    }
+
+    #[inline]
+    pub fn get_available_monitors(&self) -> Vec<MonitorId> {
+        (0..MONITORS.len()).map(|index| MonitorId(index as u32)).collect()
+    }
+
+    #[inline]
+    pub fn get_primary_monitor(&self) -> MonitorId {
+        MonitorId(0)
+    }
+
+    #[inline]
+    pub fn event_sources(&mut self) -> &mut EventSources {
+        &mut self.sources
+    }
+
+    #[inline]
+    pub fn get_display_fd(&self) -> RawFd {
+        self.shared.read
+    }
+
+    pub fn poll_events<F>(&mut self, mut callback: F)
+        where F: FnMut(Event)
+    {
+        for event in self.sources.poll() {
+            callback(event);
+        }
+        self.dispatch_queued(&mut callback);
+    }
+
+    #[inline]
+    pub fn dispatch_ready<F>(&mut self, callback: F)
+        where F: FnMut(Event)
+    {
+        self.poll_events(callback);
+    }
+
+    pub fn poll_events_timeout<F>(&mut self, timeout: Duration, mut callback: F)
+        where F: FnMut(Event)
+    {
+        let ready = self.sources.wait(self.shared.read, Some(Instant::now() + timeout));
+        for event in ready.events {
+            callback(event);
+        }
+        self.dispatch_queued(&mut callback);
+    }
+
+    pub fn run_forever<F>(&mut self, mut callback: F)
+        where F: FnMut(Event) -> ControlFlow
+    {
+        let mut control_flow = ControlFlow::Continue;
+
+        loop {
+            // Everything that was queued has been delivered at this point.
+            let flow = callback(Event::EventsCleared);
+            if control_flow != ControlFlow::Break {
+                control_flow = flow;
+            }
+
+            let deadline = match control_flow {
+                ControlFlow::Continue => None,
+                ControlFlow::Poll => Some(Instant::now()),
+                ControlFlow::WaitUntil(deadline) => Some(deadline),
+                ControlFlow::Break => break,
+            };
+            let ready = self.sources.wait(self.shared.read, deadline);
+            let timed_out = !ready.display && ready.events.is_empty();
+
+            // Track the control flow returned by the callback. `Break` always takes precedence.
+            let mut cb = |event| {
+                let flow = callback(event);
+                if control_flow != ControlFlow::Break {
+                    control_flow = flow;
+                }
+            };
+
+            for event in ready.events {
+                cb(event);
+            }
+            self.dispatch_queued(&mut cb);
+            if timed_out {
+                cb(Event::ResumeTimeReached);
+            }
+        }
+    }
+
+    // Delivers the events that are currently queued. The events queued by `callback` are left for
+    // the next call, which won't block since they are signalled through the pipe.
+    fn dispatch_queued<F>(&self, callback: &mut F)
+        where F: FnMut(Event)
+    {
+        self.shared.drain_pipe();
+        if self.shared.pending_wakeup.swap(false, Ordering::Relaxed) {
+            callback(Event::Awakened);
+        }
+
+        let events = mem::replace(&mut *self.shared.queue.lock().unwrap(), VecDeque::new());
+        for event in events {
+            callback(event);
+        }
+    }
+}
+
+impl EventsLoopProxy {
+    pub fn wakeup(&self) -> Result<(), EventsLoopClosed> {
+        let shared = match self.shared.upgrade() {
+            Some(shared) => shared,
+            None => return Err(EventsLoopClosed),
+        };
+        shared.pending_wakeup.store(true, Ordering::Relaxed);
+        shared.notify();
+        Ok(())
+    }
+}
+
+/// Pushes synthetic input into a headless `EventsLoop`, and inspects the state of its windows.
+///
+/// The events are delivered by the events loop in the order they were pushed, as if they had
+/// been received from a display server. An `Injector` can be sent to another thread, which wakes
+/// up the events loop when it pushes an event.
+///
+/// All the methods panic if they are passed a window that doesn't belong to a headless events
+/// loop, and return `EventsLoopClosed` if the events loop has been destroyed.
+#[derive(Clone)]
+pub struct Injector {
+    shared: Weak<Shared>,
+}
+
+impl Injector {
+    /// The device that all the events pushed by injectors come from.
+    #[inline]
+    pub fn device_id(&self) -> ::DeviceId {
+        ::DeviceId(super::DeviceId::Headless(DeviceId))
+    }
+
+    /// Pushes an arbitrary event for `window`.
+    pub fn window_event(&self, window: ::WindowId, event: WindowEvent) -> Result<(), EventsLoopClosed> {
+        let shared = try!(self.shared());
+        shared.push_window_event(headless_window_id(window), event);
+        Ok(())
+    }
+
+    /// Pushes a key press or release.
+    ///
+    /// No `ReceivedCharacter` event is generated, use `received_character` for that.
+    pub fn keyboard_input(&self, window: ::WindowId, state: ElementState, scancode: ScanCode,
+                          virtual_keycode: Option<VirtualKeyCode>, modifiers: ModifiersState)
+                          -> Result<(), EventsLoopClosed>
+    {
+        self.window_event(window, WindowEvent::KeyboardInput {
+            device_id: self.device_id(),
+            input: KeyboardInput {
+                scancode: scancode,
+                state: state,
+                virtual_keycode: virtual_keycode,
+                modifiers: modifiers,
+            },
+        })
+    }
+
+    /// Pushes a character typed in the window.
+    #[inline]
+    pub fn received_character(&self, window: ::WindowId, character: char) -> Result<(), EventsLoopClosed> {
+        self.window_event(window, WindowEvent::ReceivedCharacter(character))
+    }
+
+    /// Moves the cursor to `position`, relative to the top-left corner of the window.
+    #[inline]
+    pub fn mouse_moved(&self, window: ::WindowId, position: LogicalPosition) -> Result<(), EventsLoopClosed> {
+        self.window_event(window, WindowEvent::MouseMoved {
+            device_id: self.device_id(),
+            position: position,
+        })
+    }
+
+    /// Presses or releases a mouse button.
+    #[inline]
+    pub fn mouse_input(&self, window: ::WindowId, state: ElementState, button: MouseButton)
+                       -> Result<(), EventsLoopClosed>
+    {
+        self.window_event(window, WindowEvent::MouseInput {
+            device_id: self.device_id(),
+            state: state,
+            button: button,
+        })
+    }
+
+    /// Scrolls the mouse wheel.
+    #[inline]
+    pub fn mouse_wheel(&self, window: ::WindowId, delta: MouseScrollDelta) -> Result<(), EventsLoopClosed> {
+        self.window_event(window, WindowEvent::MouseWheel {
+            device_id: self.device_id(),
+            delta: delta,
+            phase: TouchPhase::Moved,
+        })
+    }
+
+    /// Pushes a touch event of the finger `id` at `location`, relative to the top-left corner of
+    /// the window.
+    #[inline]
+    pub fn touch(&self, window: ::WindowId, phase: TouchPhase, location: LogicalPosition, id: u64)
+                 -> Result<(), EventsLoopClosed>
+    {
+        self.window_event(window, WindowEvent::Touch(Touch {
+            device_id: self.device_id(),
+            phase: phase,
+            location: location,
+            id: id,
+        }))
+    }
+
+    /// Resizes the window as if the user had done it, and pushes the corresponding `Resized`
+    /// event. The size constraints of the window are ignored.
+    pub fn resize(&self, window: ::WindowId, size: LogicalSize) -> Result<(), EventsLoopClosed> {
+        let shared = try!(self.shared());
+        let id = headless_window_id(window);
+        if let Some(state) = shared.windows.lock().unwrap().get_mut(&id) {
+            state.size = size;
+        }
+        shared.push_window_event(id, WindowEvent::Resized(size));
+        Ok(())
+    }
+
+    /// Gives the keyboard focus to the window or takes it away, as if the user had done it, and
+    /// pushes the corresponding `Focused` and `StateChanged` events.
+    pub fn focus(&self, window: ::WindowId, focused: bool) -> Result<(), EventsLoopClosed> {
+        let shared = try!(self.shared());
+        let id = headless_window_id(window);
+        let changed = match shared.windows.lock().unwrap().get_mut(&id) {
+            Some(ref mut state) if state.focused != focused => {
+                state.focused = focused;
+                Some(state.root_state())
+            },
+            _ => None,
+        };
+        shared.push_window_event(id, WindowEvent::Focused(focused));
+        if let Some(state) = changed {
+            shared.push_window_event(id, WindowEvent::StateChanged(state));
+        }
+        Ok(())
+    }
+
+    /// Returns the title of the window, or `None` if the window has been destroyed.
+    pub fn title(&self, window: ::WindowId) -> Result<Option<String>, EventsLoopClosed> {
+        self.with_state(window, |state| state.title.clone())
+    }
+
+    /// Returns true if the window is visible, or `None` if the window has been destroyed.
+    pub fn is_visible(&self, window: ::WindowId) -> Result<Option<bool>, EventsLoopClosed> {
+        self.with_state(window, |state| state.visible)
+    }
+
+    /// Returns true if the window can be resized by the user, or `None` if the window has been
+    /// destroyed.
+    pub fn is_resizable(&self, window: ::WindowId) -> Result<Option<bool>, EventsLoopClosed> {
+        self.with_state(window, |state| state.resizable)
+    }
+
+    /// Returns true if the window is maximized, or `None` if the window has been destroyed.
+    pub fn is_maximized(&self, window: ::WindowId) -> Result<Option<bool>, EventsLoopClosed> {
+        self.with_state(window, |state| state.maximized)
+    }
+
+    /// Returns true if the window stays above the other windows, or `None` if the window has been
+    /// destroyed.
+    pub fn is_always_on_top(&self, window: ::WindowId) -> Result<Option<bool>, EventsLoopClosed> {
+        self.with_state(window, |state| state.always_on_top)
+    }
+
+    /// Returns the monitor the window is fullscreen on, or `None` if the window isn't fullscreen
+    /// or has been destroyed.
+    pub fn fullscreen(&self, window: ::WindowId) -> Result<Option<RootMonitorId>, EventsLoopClosed> {
+        self.with_state(window, |state| {
+            state.fullscreen.as_ref().map(|&(ref monitor, _, _)| RootMonitorId {
+                inner: super::MonitorId::Headless(monitor.clone()),
+            })
+        }).map(|fullscreen| fullscreen.and_then(|monitor| monitor))
+    }
+
+    fn with_state<F, R>(&self, window: ::WindowId, f: F) -> Result<Option<R>, EventsLoopClosed>
+        where F: FnOnce(&WindowState) -> R
+    {
+        let shared = try!(self.shared());
+        let windows = shared.windows.lock().unwrap();
+        Ok(windows.get(&headless_window_id(window)).map(f))
+    }
+
+    #[inline]
+    fn shared(&self) -> Result<Arc<Shared>, EventsLoopClosed> {
+        self.shared.upgrade().ok_or(EventsLoopClosed)
+    }
+}
+
+fn headless_window_id(window: ::WindowId) -> WindowId {
+    match window.0 {
+        super::WindowId::Headless(id) => id,
+        _ => panic!("the window doesn't belong to a headless events loop"),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowId(usize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeviceId;
+
+// The index of the monitor in `MONITORS`.
+//
+// As with the other backends, the getters return physical pixels as plain tuples, which the root
+// `MonitorId` converts to `PhysicalPosition` and `PhysicalSize`.
+#[derive(Debug, Clone)]
+pub struct MonitorId(u32);
+
+impl MonitorId {
+    #[inline]
+    pub fn get_name(&self) -> Option<String> {
+        Some(format!("Headless {}", self.0))
+    }
+
+    #[inline]
+    pub fn get_native_identifier(&self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    pub fn get_dimensions(&self) -> (u32, u32) {
+        MONITORS[self.0 as usize].1
+    }
+
+    #[inline]
+    pub fn get_position(&self) -> (i32, i32) {
+        MONITORS[self.0 as usize].0
+    }
+
+    #[inline]
+    pub fn get_hidpi_factor(&self) -> f64 {
+        1.0
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        Some(60)
+    }
+
+    // There are no panels.
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        Some((self.get_position(), self.get_dimensions()))
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> SubpixelOrder {
+        SubpixelOrder::Unknown
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        format!("headless {}", self.0)
+    }
+
+    // Headless monitors only support their current mode.
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        vec![VideoMode {
+            dimensions: self.get_dimensions(),
+            refresh_rate: 60,
+            bit_depth: 24,
+            monitor: RootMonitorId { inner: super::MonitorId::Headless(self.clone()) },
+            native_mode: 0,
+        }]
+    }
+
+    fn contains(&self, position: LogicalPosition) -> bool {
+        let (left, top) = self.get_position();
+        let (width, height) = self.get_dimensions();
+        position.x >= left as f64 && position.y >= top as f64
+            && position.x < (left + width as i32) as f64 && position.y < (top + height as i32) as f64
+    }
+}
+
+pub struct Window {
+    id: WindowId,
+    shared: Weak<Shared>,
+}
+
+impl Window {
+    pub fn new(events_loop: &EventsLoop, attributes: &WindowAttributes) -> Result<Window, CreationError> {
+        let shared = &events_loop.shared;
+        let id = WindowId(shared.next_window_id.fetch_add(1, Ordering::Relaxed));
+
+        let mut state = WindowState {
+            title: attributes.title.clone(),
+            position: LogicalPosition::new(0.0, 0.0),
+            size: LogicalSize::new(0.0, 0.0),
+            min_size: attributes.min_dimensions,
+            max_size: attributes.max_dimensions,
+            resizable: attributes.resizable,
+            visible: attributes.visible,
+            maximized: attributes.maximized,
+            minimized: false,
+            focused: false,
+            always_on_top: attributes.always_on_top,
+            fullscreen: None,
+        };
+        state.size = state.clamp_size(attributes.dimensions.unwrap_or(LogicalSize::new(800.0, 600.0)));
+        shared.windows.lock().unwrap().insert(id, state);
+
+        let window = Window {
+            id: id,
+            shared: Arc::downgrade(shared),
+        };
+        if let Some(ref fullscreen) = attributes.fullscreen {
+            window.set_fullscreen(Some(fullscreen.clone()));
+        }
+        Ok(window)
+    }
+
+    #[inline]
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    pub fn set_title(&self, title: &str) {
+        self.with_state(|state| state.title = title.to_owned());
+    }
+
+    pub fn show(&self) {
+        self.update_state(|state| state.visible = true);
+    }
+
+    pub fn hide(&self) {
+        self.update_state(|state| state.visible = false);
+    }
+
+    pub fn get_position(&self) -> Option<LogicalPosition> {
+        self.with_state(|state| state.position)
+    }
+
+    pub fn set_position(&self, position: LogicalPosition) {
+        self.move_and_resize(Some(position), None);
+    }
+
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
+        self.with_state(|state| state.size)
+    }
+
+    #[inline]
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
+        // Headless windows have no decorations.
+        self.get_inner_size()
+    }
+
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        let size = self.with_state(|state| state.clamp_size(size));
+        self.move_and_resize(None, size);
+    }
+
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        let size = self.with_state(|state| {
+            state.min_size = dimensions;
+            state.clamp_size(state.size)
+        });
+        self.move_and_resize(None, size);
+    }
+
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        let size = self.with_state(|state| {
+            state.max_size = dimensions;
+            state.clamp_size(state.size)
+        });
+        self.move_and_resize(None, size);
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, resizable: bool) {
+        self.with_state(|state| state.resizable = resizable);
+    }
+
+    #[inline]
+    pub fn set_cursor(&self, _cursor: MouseCursor) {
+    }
+
+    #[inline]
+    pub fn set_cursor_state(&self, _state: CursorState) -> Result<(), String> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn hidpi_factor(&self) -> f64 {
+        1.0
+    }
+
+    #[inline]
+    pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ()> {
+        Ok(())
+    }
+
+    pub fn set_maximized(&self, maximized: bool) {
+        self.update_state(|state| state.maximized = maximized);
+    }
+
+    pub fn set_minimized(&self, minimized: bool) {
+        self.update_state(|state| state.minimized = minimized);
+    }
+
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        self.with_state(|state| state.always_on_top = always_on_top);
+    }
+
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        // There is no video mode to switch to, exclusive fullscreen is the same as borderless.
+        // The monitors of other backends can't be used, the request is then ignored.
+        let monitor = match fullscreen.as_ref().map(|fullscreen| fullscreen.get_monitor()) {
+            Some(&RootMonitorId { inner: super::MonitorId::Headless(ref monitor) }) => Some(monitor.clone()),
+            Some(_) => {
+                eprintln!("[winit] Something's broken, got a fullscreen monitor of another backend in headless");
+                return;
+            },
+            None => None,
+        };
+
+        let target = self.update_state(|state| {
+            match monitor {
+                Some(monitor) => {
+                    let restore = match state.fullscreen.take() {
+                        Some((_, position, size)) => (position, size),
+                        None => (state.position, state.size),
+                    };
+                    let target = (monitor.get_position().into(), monitor.get_dimensions().into());
+                    state.fullscreen = Some((monitor, restore.0, restore.1));
+                    Some(target)
+                },
+                None => state.fullscreen.take().map(|(_, position, size)| (position, size)),
+            }
+        });
+
+        if let Some(Some((position, size))) = target {
+            self.move_and_resize(Some(position), Some(size));
+        }
+    }
+
+    pub fn get_state(&self) -> ::WindowState {
+        self.with_state(|state| state.root_state()).unwrap_or_default()
+    }
+
+    pub fn get_current_monitor(&self) -> MonitorId {
+        let position = self.get_position().unwrap_or(LogicalPosition::new(0.0, 0.0));
+        (0..MONITORS.len())
+            .map(|index| MonitorId(index as u32))
+            .find(|monitor| monitor.contains(position))
+            .unwrap_or(MonitorId(0))
+    }
+
+    // Updates the geometry of the window, and pushes `Moved` and `Resized` events for what
+    // changed, like a window manager would.
+    fn move_and_resize(&self, position: Option<LogicalPosition>, size: Option<LogicalSize>) {
+        let shared = match self.shared.upgrade() {
+            Some(shared) => shared,
+            None => return,
+        };
+
+        let (moved, resized) = {
+            let mut windows = shared.windows.lock().unwrap();
+            let state = match windows.get_mut(&self.id) {
+                Some(state) => state,
+                None => return,
+            };
+            let moved = position.and_then(|position| {
+                if position == state.position { return None; }
+                state.position = position;
+                Some(position)
+            });
+            let resized = size.and_then(|size| {
+                if size == state.size { return None; }
+                state.size = size;
+                Some(size)
+            });
+            (moved, resized)
+        };
+
+        if let Some(position) = moved {
+            shared.push_window_event(self.id, WindowEvent::Moved(position));
+        }
+        if let Some(size) = resized {
+            shared.push_window_event(self.id, WindowEvent::Resized(size));
+        }
+    }
+
+    fn with_state<F, R>(&self, f: F) -> Option<R>
+        where F: FnOnce(&mut WindowState) -> R
+    {
+        let shared = match self.shared.upgrade() {
+            Some(shared) => shared,
+            None => return None,
+        };
+        let mut windows = shared.windows.lock().unwrap();
+        windows.get_mut(&self.id).map(f)
+    }
+
+    // Like `with_state`, but also pushes a `StateChanged` event if `f` changed the state of the
+    // window, like a window manager would.
+    fn update_state<F, R>(&self, f: F) -> Option<R>
+        where F: FnOnce(&mut WindowState) -> R
+    {
+        let shared = match self.shared.upgrade() {
+            Some(shared) => shared,
+            None => return None,
+        };
+
+        let (result, changed) = {
+            let mut windows = shared.windows.lock().unwrap();
+            let state = match windows.get_mut(&self.id) {
+                Some(state) => state,
+                None => return None,
+            };
+            let old_state = state.root_state();
+            let result = f(state);
+            let new_state = state.root_state();
+            (result, if new_state != old_state { Some(new_state) } else { None })
+        };
+
+        if let Some(state) = changed {
+            shared.push_window_event(self.id, WindowEvent::StateChanged(state));
+        }
+        Some(result)
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        if let Some(shared) = self.shared.upgrade() {
+            shared.windows.lock().unwrap().remove(&self.id);
+        }
+    }
+}
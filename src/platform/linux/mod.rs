@@ -1,26 +1,37 @@
 #![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
 
 use std::collections::VecDeque;
+use std::ptr;
 use std::sync::Arc;
 use std::env;
+use std::os::unix::io::RawFd;
+use std::time::Duration;
 
-use {CreationError, CursorState, EventsLoopClosed, MouseCursor, ControlFlow};
+use {BackendError, CreationError, CursorState, EventsLoopClosed, EventsLoopCreationError};
+use {MouseCursor, ControlFlow};
+use dpi::{LogicalPosition, LogicalSize};
 use libc;
 
+use self::poll::EventSources;
 use self::x11::XConnection;
 use self::x11::XError;
 use self::x11::ffi::XVisualInfo;
 
+pub use self::headless::HeadlessNotSupported;
+pub use self::wayland::WaylandNotSupported;
 pub use self::x11::XNotSupported;
 use window::MonitorId as RootMonitorId;
+use window::{Fullscreen, GammaRamp, MonitorTransform, SubpixelOrder, VideoMode};
 
 mod dlopen;
+pub mod headless;
+pub mod poll;
 pub mod wayland;
 pub mod x11;
 
 /// Environment variable specifying which backend should be used on unix platform.
 ///
-/// Legal values are x11 and wayland. If this variable is set only the named backend
+/// Legal values are x11, wayland and headless. If this variable is set only the named backend
 /// will be tried by winit. If it is not set, winit will try to connect to a wayland connection,
 /// and if it fails will fallback on x11.
 ///
@@ -31,35 +42,52 @@ const BACKEND_PREFERENCE_ENV_VAR: &str = "WINIT_UNIX_BACKEND";
 pub struct PlatformSpecificWindowBuilderAttributes {
     pub visual_infos: Option<XVisualInfo>,
     pub screen_id: Option<i32>,
+    pub sticky: bool,
+    pub skip_taskbar: bool,
+    pub skip_pager: bool,
 }
 
 lazy_static!(
     pub static ref X11_BACKEND: Result<Arc<XConnection>, XNotSupported> = {
-        XConnection::new(Some(x_error_callback)).map(Arc::new)
+        XConnection::new(Some(x_error_callback), Some(x_io_error_callback)).map(Arc::new)
     };
 );
 
 pub enum Window {
     X(x11::Window),
-    Wayland(wayland::Window)
+    Wayland(wayland::Window),
+    Headless(headless::Window),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum WindowId {
     X(x11::WindowId),
-    Wayland(wayland::WindowId)
+    Wayland(wayland::WindowId),
+    Headless(headless::WindowId),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DeviceId {
     X(x11::DeviceId),
-    Wayland(wayland::DeviceId)
+    Wayland(wayland::DeviceId),
+    Headless(headless::DeviceId),
+    // A device of a recording, which doesn't exist in the current session whatever the backend.
+    // See `record::Player`.
+    Replayed(u32),
+}
+
+impl DeviceId {
+    #[inline]
+    pub fn replayed(index: u32) -> DeviceId {
+        DeviceId::Replayed(index)
+    }
 }
 
 #[derive(Clone)]
 pub enum MonitorId {
     X(x11::MonitorId),
     Wayland(wayland::MonitorId),
+    Headless(headless::MonitorId),
 }
 
 impl MonitorId {
@@ -68,6 +96,7 @@ impl MonitorId {
         match self {
             &MonitorId::X(ref m) => m.get_name(),
             &MonitorId::Wayland(ref m) => m.get_name(),
+            &MonitorId::Headless(ref m) => m.get_name(),
         }
     }
 
@@ -76,6 +105,7 @@ impl MonitorId {
         match self {
             &MonitorId::X(ref m) => m.get_native_identifier(),
             &MonitorId::Wayland(ref m) => m.get_native_identifier(),
+            &MonitorId::Headless(ref m) => m.get_native_identifier(),
         }
     }
 
@@ -84,6 +114,7 @@ impl MonitorId {
         match self {
             &MonitorId::X(ref m) => m.get_dimensions(),
             &MonitorId::Wayland(ref m) => m.get_dimensions(),
+            &MonitorId::Headless(ref m) => m.get_dimensions(),
         }
     }
 
@@ -92,14 +123,146 @@ impl MonitorId {
         match self {
             &MonitorId::X(ref m) => m.get_position(),
             &MonitorId::Wayland(ref m) => m.get_position(),
+            &MonitorId::Headless(ref m) => m.get_position(),
         }
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         match self {
             &MonitorId::X(ref m) => m.get_hidpi_factor(),
             &MonitorId::Wayland(ref m) => m.get_hidpi_factor(),
+            &MonitorId::Headless(ref m) => m.get_hidpi_factor(),
+        }
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        match self {
+            &MonitorId::X(ref m) => m.get_dimensions_mm(),
+            &MonitorId::Wayland(ref m) => m.get_dimensions_mm(),
+            &MonitorId::Headless(ref m) => m.get_dimensions_mm(),
+        }
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        match self {
+            &MonitorId::X(ref m) => m.get_refresh_rate(),
+            &MonitorId::Wayland(ref m) => m.get_refresh_rate(),
+            &MonitorId::Headless(ref m) => m.get_refresh_rate(),
+        }
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        match self {
+            &MonitorId::X(ref m) => m.get_work_area(),
+            &MonitorId::Wayland(ref m) => m.get_work_area(),
+            &MonitorId::Headless(ref m) => m.get_work_area(),
+        }
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        match self {
+            &MonitorId::X(ref m) => m.get_transform(),
+            &MonitorId::Wayland(ref m) => m.get_transform(),
+            &MonitorId::Headless(ref m) => m.get_transform(),
+        }
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> SubpixelOrder {
+        match self {
+            &MonitorId::X(ref m) => m.get_subpixel_order(),
+            &MonitorId::Wayland(ref m) => m.get_subpixel_order(),
+            &MonitorId::Headless(ref m) => m.get_subpixel_order(),
+        }
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_manufacturer(),
+            &MonitorId::Wayland(ref m) => m.get_manufacturer(),
+            &MonitorId::Headless(ref m) => m.get_manufacturer(),
+        }
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_model(),
+            &MonitorId::Wayland(ref m) => m.get_model(),
+            &MonitorId::Headless(ref m) => m.get_model(),
+        }
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        match self {
+            &MonitorId::X(ref m) => m.get_serial(),
+            &MonitorId::Wayland(ref m) => m.get_serial(),
+            &MonitorId::Headless(ref m) => m.get_serial(),
+        }
+    }
+
+    // What `PartialEq` and `Hash` of the root `MonitorId` are based on. The stable identifiers of
+    // different backends aren't comparable, so the backend is part of it.
+    #[inline]
+    pub fn get_identity(&self) -> (u8, String) {
+        match self {
+            &MonitorId::X(ref m) => (0, m.get_stable_identifier()),
+            &MonitorId::Wayland(ref m) => (1, m.get_stable_identifier()),
+            &MonitorId::Headless(ref m) => (2, m.get_stable_identifier()),
+        }
+    }
+
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        match self {
+            &MonitorId::X(ref m) => m.get_stable_identifier(),
+            &MonitorId::Wayland(ref m) => m.get_stable_identifier(),
+            &MonitorId::Headless(ref m) => m.get_stable_identifier(),
+        }
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        match self {
+            &MonitorId::X(ref m) => match *X11_BACKEND {
+                Ok(ref x) => m.get_gamma_ramp(x),
+                Err(_) => None,
+            },
+            // TODO: needs the wlr-gamma-control protocol, which isn't part of wayland-protocols 0.11
+            &MonitorId::Wayland(_) => None,
+            &MonitorId::Headless(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), ()> {
+        match self {
+            &MonitorId::X(ref m) => match *X11_BACKEND {
+                Ok(ref x) => m.set_gamma_ramp(x, ramp),
+                Err(_) => Err(()),
+            },
+            // TODO: see `get_gamma_ramp`
+            &MonitorId::Wayland(_) => Err(()),
+            &MonitorId::Headless(_) => Err(()),
+        }
+    }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<VideoMode> {
+        match self {
+            &MonitorId::X(ref m) => match *X11_BACKEND {
+                Ok(ref x) => m.get_video_modes(x),
+                Err(_) => Vec::new(),
+            },
+            &MonitorId::Wayland(_) => Vec::new(),
+            &MonitorId::Headless(ref m) => m.get_video_modes(),
         }
     }
 }
@@ -119,6 +282,10 @@ impl Window {
             EventsLoop::X(ref el) => {
                 x11::Window::new(el, window, pl_attribs).map(Window::X)
             },
+
+            EventsLoop::Headless(ref el) => {
+                headless::Window::new(el, window).map(Window::Headless)
+            },
         }
     }
 
@@ -126,7 +293,8 @@ impl Window {
     pub fn id(&self) -> WindowId {
         match self {
             &Window::X(ref w) => WindowId::X(w.id()),
-            &Window::Wayland(ref w) => WindowId::Wayland(w.id())
+            &Window::Wayland(ref w) => WindowId::Wayland(w.id()),
+            &Window::Headless(ref w) => WindowId::Headless(w.id()),
         }
     }
 
@@ -134,7 +302,8 @@ impl Window {
     pub fn set_title(&self, title: &str) {
         match self {
             &Window::X(ref w) => w.set_title(title),
-            &Window::Wayland(ref w) => w.set_title(title)
+            &Window::Wayland(ref w) => w.set_title(title),
+            &Window::Headless(ref w) => w.set_title(title),
         }
     }
 
@@ -142,7 +311,8 @@ impl Window {
     pub fn show(&self) {
         match self {
             &Window::X(ref w) => w.show(),
-            &Window::Wayland(ref w) => w.show()
+            &Window::Wayland(ref w) => w.show(),
+            &Window::Headless(ref w) => w.show(),
         }
     }
 
@@ -150,47 +320,80 @@ impl Window {
     pub fn hide(&self) {
         match self {
             &Window::X(ref w) => w.hide(),
-            &Window::Wayland(ref w) => w.hide()
+            &Window::Wayland(ref w) => w.hide(),
+            &Window::Headless(ref w) => w.hide(),
         }
     }
 
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         match self {
             &Window::X(ref w) => w.get_position(),
-            &Window::Wayland(ref w) => w.get_position()
+            &Window::Wayland(ref w) => w.get_position(),
+            &Window::Headless(ref w) => w.get_position(),
         }
     }
 
     #[inline]
-    pub fn set_position(&self, x: i32, y: i32) {
+    pub fn set_position(&self, position: LogicalPosition) {
         match self {
-            &Window::X(ref w) => w.set_position(x, y),
-            &Window::Wayland(ref w) => w.set_position(x, y)
+            &Window::X(ref w) => w.set_position(position),
+            &Window::Wayland(ref w) => w.set_position(position),
+            &Window::Headless(ref w) => w.set_position(position),
         }
     }
 
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         match self {
             &Window::X(ref w) => w.get_inner_size(),
-            &Window::Wayland(ref w) => w.get_inner_size()
+            &Window::Wayland(ref w) => w.get_inner_size(),
+            &Window::Headless(ref w) => w.get_inner_size(),
         }
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         match self {
             &Window::X(ref w) => w.get_outer_size(),
-            &Window::Wayland(ref w) => w.get_outer_size()
+            &Window::Wayland(ref w) => w.get_outer_size(),
+            &Window::Headless(ref w) => w.get_outer_size(),
+        }
+    }
+
+    #[inline]
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        match self {
+            &Window::X(ref w) => w.set_inner_size(size),
+            &Window::Wayland(ref w) => w.set_inner_size(size),
+            &Window::Headless(ref w) => w.set_inner_size(size),
+        }
+    }
+
+    #[inline]
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        match self {
+            &Window::X(ref w) => w.set_min_dimensions(dimensions),
+            &Window::Wayland(ref w) => w.set_min_dimensions(dimensions),
+            &Window::Headless(ref w) => w.set_min_dimensions(dimensions),
+        }
+    }
+
+    #[inline]
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        match self {
+            &Window::X(ref w) => w.set_max_dimensions(dimensions),
+            &Window::Wayland(ref w) => w.set_max_dimensions(dimensions),
+            &Window::Headless(ref w) => w.set_max_dimensions(dimensions),
         }
     }
 
     #[inline]
-    pub fn set_inner_size(&self, x: u32, y: u32) {
+    pub fn set_resizable(&self, resizable: bool) {
         match self {
-            &Window::X(ref w) => w.set_inner_size(x, y),
-            &Window::Wayland(ref w) => w.set_inner_size(x, y)
+            &Window::X(ref w) => w.set_resizable(resizable),
+            &Window::Wayland(ref w) => w.set_resizable(resizable),
+            &Window::Headless(ref w) => w.set_resizable(resizable),
         }
     }
 
@@ -198,7 +401,8 @@ impl Window {
     pub fn set_cursor(&self, cursor: MouseCursor) {
         match self {
             &Window::X(ref w) => w.set_cursor(cursor),
-            &Window::Wayland(ref w) => w.set_cursor(cursor)
+            &Window::Wayland(ref w) => w.set_cursor(cursor),
+            &Window::Headless(ref w) => w.set_cursor(cursor),
         }
     }
 
@@ -206,23 +410,26 @@ impl Window {
     pub fn set_cursor_state(&self, state: CursorState) -> Result<(), String> {
         match self {
             &Window::X(ref w) => w.set_cursor_state(state),
-            &Window::Wayland(ref w) => w.set_cursor_state(state)
+            &Window::Wayland(ref w) => w.set_cursor_state(state),
+            &Window::Headless(ref w) => w.set_cursor_state(state),
         }
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
+    pub fn hidpi_factor(&self) -> f64 {
        match self {
             &Window::X(ref w) => w.hidpi_factor(),
-            &Window::Wayland(ref w) => w.hidpi_factor()
+            &Window::Wayland(ref w) => w.hidpi_factor(),
+            &Window::Headless(ref w) => w.hidpi_factor(),
         }
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, x: i32, y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), ()> {
         match self {
-            &Window::X(ref w) => w.set_cursor_position(x, y),
-            &Window::Wayland(ref w) => w.set_cursor_position(x, y)
+            &Window::X(ref w) => w.set_cursor_position(position),
+            &Window::Wayland(ref w) => w.set_cursor_position(position),
+            &Window::Headless(ref w) => w.set_cursor_position(position),
         }
     }
 
@@ -231,7 +438,8 @@ impl Window {
         use wayland_client::Proxy;
         match self {
             &Window::X(ref w) => w.platform_display(),
-            &Window::Wayland(ref w) => w.get_display().ptr() as *mut _
+            &Window::Wayland(ref w) => w.get_display().ptr() as *mut _,
+            &Window::Headless(_) => ptr::null_mut(),
         }
     }
 
@@ -240,7 +448,8 @@ impl Window {
         use wayland_client::Proxy;
         match self {
             &Window::X(ref w) => w.platform_window(),
-            &Window::Wayland(ref w) => w.get_surface().ptr() as *mut _
+            &Window::Wayland(ref w) => w.get_surface().ptr() as *mut _,
+            &Window::Headless(_) => ptr::null_mut(),
         }
     }
 
@@ -249,14 +458,43 @@ impl Window {
         match self {
             &Window::X(ref w) => w.set_maximized(maximized),
             &Window::Wayland(ref _w) => {},
+            &Window::Headless(ref w) => w.set_maximized(maximized),
         }
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
+    pub fn set_always_on_top(&self, always_on_top: bool) {
         match self {
-            &Window::X(ref w) => w.set_fullscreen(monitor),
+            &Window::X(ref w) => w.set_always_on_top(always_on_top),
             &Window::Wayland(ref _w) => {},
+            &Window::Headless(ref w) => w.set_always_on_top(always_on_top),
+        }
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        match self {
+            &Window::X(ref w) => w.set_minimized(minimized),
+            &Window::Wayland(ref w) => w.set_minimized(minimized),
+            &Window::Headless(ref w) => w.set_minimized(minimized),
+        }
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        match self {
+            &Window::X(ref w) => w.set_fullscreen(fullscreen),
+            &Window::Wayland(ref _w) => {},
+            &Window::Headless(ref w) => w.set_fullscreen(fullscreen),
+        }
+    }
+
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        match self {
+            &Window::X(ref w) => w.get_state(),
+            &Window::Wayland(ref w) => w.get_state(),
+            &Window::Headless(ref w) => w.get_state(),
         }
     }
 
@@ -265,6 +503,7 @@ impl Window {
         match self {
             &Window::X(ref w) => RootMonitorId{inner: MonitorId::X(w.get_current_monitor())},
             &Window::Wayland(ref w) => RootMonitorId{inner: MonitorId::Wayland(w.get_current_monitor())},
+            &Window::Headless(ref w) => RootMonitorId{inner: MonitorId::Headless(w.get_current_monitor())},
         }
     }
 }
@@ -292,50 +531,73 @@ unsafe extern "C" fn x_error_callback(dpy: *mut x11::ffi::Display, event: *mut x
     0
 }
 
+// Called by Xlib when the connection to the X server breaks during an Xlib call. Xlib terminates
+// the process when this returns, and the only way to prevent that is to `longjmp` out of the
+// handler, which can't be done soundly from Rust. All we can do is to explain why.
+//
+// Recovering is therefore only possible when the events loop notices the loss before Xlib does:
+// it checks the socket before reading from it and reports `Event::ConnectionLost` instead. If the
+// connection breaks during any other Xlib call, such as a `Window` method, the process exits.
+unsafe extern "C" fn x_io_error_callback(_dpy: *mut x11::ffi::Display) -> libc::c_int {
+    use std::io::Write;
+
+    let _ = writeln!(::std::io::stderr(), "[winit] the connection to the X server has been lost");
+    0
+}
+
 pub enum EventsLoop {
     Wayland(wayland::EventsLoop),
-    X(x11::EventsLoop)
+    X(x11::EventsLoop),
+    Headless(headless::EventsLoop),
 }
 
 #[derive(Clone)]
 pub enum EventsLoopProxy {
     X(x11::EventsLoopProxy),
     Wayland(wayland::EventsLoopProxy),
+    Headless(headless::EventsLoopProxy),
 }
 
 impl EventsLoop {
-    pub fn new() -> EventsLoop {
+    pub fn try_new() -> Result<EventsLoop, EventsLoopCreationError> {
         if let Ok(env_var) = env::var(BACKEND_PREFERENCE_ENV_VAR) {
-            match env_var.as_str() {
-                "x11" => {
-                    return EventsLoop::new_x11().unwrap();      // TODO: propagate
-                },
-                "wayland" => {
-                    match EventsLoop::new_wayland() {
-                        Ok(e) => return e,
-                        Err(_) => panic!()      // TODO: propagate
-                    }
-                },
-                _ => panic!("Unknown environment variable value for {}, try one of `x11`,`wayland`",
-                            BACKEND_PREFERENCE_ENV_VAR),
-            }
-        }
+            return match env_var.as_str() {
+                "x11" => EventsLoop::new_x11().map_err(|err| {
+                    EventsLoopCreationError::NoBackendAvailable(vec![BackendError::X11(err)])
+                }),
+                "wayland" => EventsLoop::new_wayland().map_err(|err| {
+                    EventsLoopCreationError::NoBackendAvailable(vec![BackendError::Wayland(err)])
+                }),
+                "headless" => EventsLoop::new_headless().map_err(|err| {
+                    EventsLoopCreationError::NoBackendAvailable(vec![BackendError::Headless(err)])
+                }),
+                _ => Err(EventsLoopCreationError::UnknownBackend(env_var.clone())),
+            };
+        }
+
+        let wayland_err = match EventsLoop::new_wayland() {
+            Ok(el) => return Ok(el),
+            Err(err) => err,
+        };
 
-        if let Ok(el) = EventsLoop::new_wayland() {
-            return el;
-        }
+        let x11_err = match EventsLoop::new_x11() {
+            Ok(el) => return Ok(el),
+            Err(err) => err,
+        };
 
-        if let Ok(el) = EventsLoop::new_x11() {
-            return el;
-        }
+        Err(EventsLoopCreationError::NoBackendAvailable(vec![
+            BackendError::Wayland(wayland_err),
+            BackendError::X11(x11_err),
+        ]))
+    }
 
-        panic!("No backend is available")
+    pub fn new_wayland() -> Result<EventsLoop, WaylandNotSupported> {
+        wayland::EventsLoop::new().map(EventsLoop::Wayland)
     }
 
-    pub fn new_wayland() -> Result<EventsLoop, ()> {
-        wayland::EventsLoop::new()
-            .map(EventsLoop::Wayland)
-            .ok_or(())
+    #[inline]
+    pub fn new_headless() -> Result<EventsLoop, HeadlessNotSupported> {
+        headless::EventsLoop::new().map(EventsLoop::Headless)
     }
 
     pub fn new_x11() -> Result<EventsLoop, XNotSupported> {
@@ -356,6 +618,10 @@ impl EventsLoop {
                                         .into_iter()
                                         .map(MonitorId::X)
                                         .collect(),
+            EventsLoop::Headless(ref evlp) => evlp.get_available_monitors()
+                                        .into_iter()
+                                        .map(MonitorId::Headless)
+                                        .collect(),
         }
     }
 
@@ -364,6 +630,7 @@ impl EventsLoop {
         match *self {
             EventsLoop::Wayland(ref evlp) => MonitorId::Wayland(evlp.get_primary_monitor()),
             EventsLoop::X(ref evlp) => MonitorId::X(x11::get_primary_monitor(evlp.x_connection())),
+            EventsLoop::Headless(ref evlp) => MonitorId::Headless(evlp.get_primary_monitor()),
         }
     }
 
@@ -371,6 +638,7 @@ impl EventsLoop {
         match *self {
             EventsLoop::Wayland(ref evlp) => EventsLoopProxy::Wayland(evlp.create_proxy()),
             EventsLoop::X(ref evlp) => EventsLoopProxy::X(evlp.create_proxy()),
+            EventsLoop::Headless(ref evlp) => EventsLoopProxy::Headless(evlp.create_proxy()),
         }
     }
 
@@ -379,7 +647,28 @@ impl EventsLoop {
     {
         match *self {
             EventsLoop::Wayland(ref mut evlp) => evlp.poll_events(callback),
-            EventsLoop::X(ref mut evlp) => evlp.poll_events(callback)
+            EventsLoop::X(ref mut evlp) => evlp.poll_events(callback),
+            EventsLoop::Headless(ref mut evlp) => evlp.poll_events(callback),
+        }
+    }
+
+    pub fn poll_events_timeout<F>(&mut self, timeout: Duration, callback: F)
+        where F: FnMut(::Event)
+    {
+        match *self {
+            EventsLoop::Wayland(ref mut evlp) => evlp.poll_events_timeout(timeout, callback),
+            EventsLoop::X(ref mut evlp) => evlp.poll_events_timeout(timeout, callback),
+            EventsLoop::Headless(ref mut evlp) => evlp.poll_events_timeout(timeout, callback),
+        }
+    }
+
+    pub fn dispatch_ready<F>(&mut self, callback: F)
+        where F: FnMut(::Event)
+    {
+        match *self {
+            EventsLoop::Wayland(ref mut evlp) => evlp.dispatch_ready(callback),
+            EventsLoop::X(ref mut evlp) => evlp.dispatch_ready(callback),
+            EventsLoop::Headless(ref mut evlp) => evlp.dispatch_ready(callback),
         }
     }
 
@@ -388,7 +677,8 @@ impl EventsLoop {
     {
         match *self {
             EventsLoop::Wayland(ref mut evlp) => evlp.run_forever(callback),
-            EventsLoop::X(ref mut evlp) => evlp.run_forever(callback)
+            EventsLoop::X(ref mut evlp) => evlp.run_forever(callback),
+            EventsLoop::Headless(ref mut evlp) => evlp.run_forever(callback),
         }
     }
 
@@ -396,15 +686,57 @@ impl EventsLoop {
     pub fn is_wayland(&self) -> bool {
         match *self {
             EventsLoop::Wayland(_) => true,
-            EventsLoop::X(_) => false,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_x11(&self) -> bool {
+        match *self {
+            EventsLoop::X(_) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        match *self {
+            EventsLoop::Headless(_) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn headless_injector(&self) -> Option<headless::Injector> {
+        match *self {
+            EventsLoop::Headless(ref evlp) => Some(evlp.create_injector()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn event_sources(&mut self) -> &mut EventSources {
+        match *self {
+            EventsLoop::Wayland(ref mut evlp) => evlp.event_sources(),
+            EventsLoop::X(ref mut evlp) => evlp.event_sources(),
+            EventsLoop::Headless(ref mut evlp) => evlp.event_sources(),
+        }
+    }
+
+    #[inline]
+    pub fn get_display_fd(&self) -> RawFd {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.get_display_fd(),
+            EventsLoop::X(ref evlp) => evlp.get_display_fd(),
+            EventsLoop::Headless(ref evlp) => evlp.get_display_fd(),
         }
     }
 
     #[inline]
     pub fn x_connection(&self) -> Option<&Arc<XConnection>> {
         match *self {
-            EventsLoop::Wayland(_) => None,
             EventsLoop::X(ref ev) => Some(ev.x_connection()),
+            _ => None,
         }
     }
 }
@@ -414,6 +746,7 @@ impl EventsLoopProxy {
         match *self {
             EventsLoopProxy::Wayland(ref proxy) => proxy.wakeup(),
             EventsLoopProxy::X(ref proxy) => proxy.wakeup(),
+            EventsLoopProxy::Headless(ref proxy) => proxy.wakeup(),
         }
     }
 }
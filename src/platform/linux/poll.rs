@@ -0,0 +1,314 @@
+use std::cmp;
+use std::io;
+use std::mem;
+use std::ptr;
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicIsize, Ordering, ATOMIC_ISIZE_INIT};
+use std::time::Instant;
+
+use libc;
+
+use {Event, Readiness, Signal};
+
+/// The file descriptors and signals watched by an events loop in addition to the connection to
+/// the display server.
+pub struct EventSources {
+    fds: Vec<FdSource>,
+    signal_pipe: Option<SignalPipe>,
+}
+
+struct FdSource {
+    fd: RawFd,
+    token: usize,
+    interest: Readiness,
+}
+
+/// The result of `EventSources::wait`.
+pub struct Ready {
+    /// True if the connection to the display server is readable.
+    pub display: bool,
+    /// The events generated by the sources that are ready.
+    pub events: Vec<Event>,
+}
+
+impl EventSources {
+    pub fn new() -> EventSources {
+        EventSources {
+            fds: Vec::new(),
+            signal_pipe: None,
+        }
+    }
+
+    /// Starts watching `fd`, replacing the previous registration of `fd` if there is one.
+    pub fn register_fd(&mut self, fd: RawFd, token: usize, interest: Readiness) {
+        self.unregister_fd(fd);
+        self.fds.push(FdSource {
+            fd: fd,
+            token: token,
+            interest: interest,
+        });
+    }
+
+    /// Stops watching `fd`. Does nothing if `fd` isn't registered.
+    pub fn unregister_fd(&mut self, fd: RawFd) {
+        self.fds.retain(|source| source.fd != fd);
+    }
+
+    /// Installs handlers for `signals` that report them as events of these sources.
+    pub fn watch_signals(&mut self, signals: &[Signal]) -> io::Result<()> {
+        if self.signal_pipe.is_none() {
+            self.signal_pipe = Some(try!(SignalPipe::new()));
+        }
+
+        let pipe = self.signal_pipe.as_mut().unwrap();
+        for &signal in signals {
+            try!(pipe.watch(signal));
+        }
+        Ok(())
+    }
+
+    /// Blocks the current thread until `display_fd` is readable, one of the sources is ready or
+    /// `deadline` is reached.
+    ///
+    /// A `deadline` of `None` means waiting forever.
+    pub fn wait(&self, display_fd: RawFd, deadline: Option<Instant>) -> Ready {
+        self.poll_fds(Some(display_fd), deadline)
+    }
+
+    /// Returns the events of the sources that are ready, without blocking.
+    pub fn poll(&self) -> Vec<Event> {
+        if self.fds.is_empty() && self.signal_pipe.is_none() {
+            return Vec::new();
+        }
+        self.poll_fds(None, Some(Instant::now())).events
+    }
+
+    fn poll_fds(&self, display_fd: Option<RawFd>, deadline: Option<Instant>) -> Ready {
+        let mut pollfds = Vec::with_capacity(self.fds.len() + 2);
+        if let Some(fd) = display_fd {
+            pollfds.push(pollfd(fd, libc::POLLIN));
+        }
+        if let Some(ref pipe) = self.signal_pipe {
+            pollfds.push(pollfd(pipe.read, libc::POLLIN));
+        }
+        for source in &self.fds {
+            let mut events = 0;
+            if source.interest.readable {
+                events |= libc::POLLIN;
+            }
+            if source.interest.writable {
+                events |= libc::POLLOUT;
+            }
+            pollfds.push(pollfd(source.fd, events));
+        }
+
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => timeout_ms(deadline),
+                None => -1,
+            };
+
+            match unsafe { libc::poll(pollfds.as_mut_ptr(), pollfds.len() as libc::nfds_t, timeout) } {
+                0 => return Ready { display: false, events: Vec::new() },
+                ret if ret > 0 => break,
+                _ => {
+                    // Retry if we were interrupted by a signal. On any other error we pretend the
+                    // display is readable, so that the caller's next read reports the actual
+                    // problem.
+                    if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+                        return Ready { display: display_fd.is_some(), events: Vec::new() };
+                    }
+                },
+            }
+        }
+
+        let mut pollfds = pollfds.iter();
+        let display = match display_fd {
+            Some(_) => pollfds.next().unwrap().revents != 0,
+            None => false,
+        };
+
+        let mut events = Vec::new();
+        if let Some(ref pipe) = self.signal_pipe {
+            if pollfds.next().unwrap().revents != 0 {
+                pipe.read_signals(&mut events);
+            }
+        }
+        for (source, pollfd) in self.fds.iter().zip(pollfds) {
+            if pollfd.revents != 0 {
+                events.push(Event::FdReady {
+                    token: source.token,
+                    readiness: Readiness {
+                        readable: pollfd.revents & libc::POLLIN != 0,
+                        writable: pollfd.revents & libc::POLLOUT != 0,
+                        error: pollfd.revents & (libc::POLLERR | libc::POLLHUP | libc::POLLNVAL) != 0,
+                    },
+                });
+            }
+        }
+
+        Ready { display: display, events: events }
+    }
+}
+
+#[inline]
+fn pollfd(fd: RawFd, events: libc::c_short) -> libc::pollfd {
+    libc::pollfd {
+        fd: fd,
+        events: events,
+        revents: 0,
+    }
+}
+
+// Converts a deadline to a `poll` timeout in milliseconds.
+//
+// The value is rounded up so that we never wake up before the deadline.
+fn timeout_ms(deadline: Instant) -> libc::c_int {
+    let now = Instant::now();
+    if deadline <= now {
+        return 0;
+    }
+
+    let remaining = deadline - now;
+    let ms = remaining.as_secs()
+        .saturating_mul(1000)
+        .saturating_add(((remaining.subsec_nanos() + 999_999) / 1_000_000) as u64);
+    cmp::min(ms, libc::c_int::max_value() as u64) as libc::c_int
+}
+
+/// Creates a pipe whose ends are non-blocking and closed on `exec`. Returns the read end and the
+/// write end, in that order.
+pub fn nonblocking_pipe() -> io::Result<(RawFd, RawFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    for &fd in &fds {
+        unsafe {
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            if flags < 0 || libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0
+                || libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) < 0
+            {
+                let err = io::Error::last_os_error();
+                libc::close(fds[0]);
+                libc::close(fds[1]);
+                return Err(err);
+            }
+        }
+    }
+
+    Ok((fds[0], fds[1]))
+}
+
+// The write end of the pipe of the `SignalPipe` that receives the signals, or 0 if there is none.
+//
+// Signal handlers are process-wide, so only the events loop that called `watch_signals` last
+// receives them. The write end of a pipe is never 0, since the read end is allocated first.
+static SIGNAL_PIPE_WRITE: AtomicIsize = ATOMIC_ISIZE_INIT;
+
+// A self-pipe: the signal handler writes the number of the signal to the pipe, which wakes up the
+// events loop waiting on the read end.
+struct SignalPipe {
+    read: RawFd,
+    write: RawFd,
+    // The signals we installed a handler for, and the handler that was installed before.
+    previous_handlers: Vec<(libc::c_int, libc::sigaction)>,
+}
+
+impl SignalPipe {
+    fn new() -> io::Result<SignalPipe> {
+        // Neither the handler nor `read_signals` may block.
+        let (read, write) = try!(nonblocking_pipe());
+        Ok(SignalPipe {
+            read: read,
+            write: write,
+            previous_handlers: Vec::new(),
+        })
+    }
+
+    fn watch(&mut self, signal: Signal) -> io::Result<()> {
+        let signum = signal_number(signal);
+        if self.previous_handlers.iter().any(|&(s, _)| s == signum) {
+            return Ok(());
+        }
+
+        unsafe {
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = signal_handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            let mut previous: libc::sigaction = mem::zeroed();
+            if libc::sigaction(signum, &action, &mut previous) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            self.previous_handlers.push((signum, previous));
+        }
+
+        // Another events loop may have taken over the signals in the meantime.
+        SIGNAL_PIPE_WRITE.store(self.write as isize, Ordering::SeqCst);
+        Ok(())
+    }
+
+    // Empties the pipe and appends a `Signal` event to `events` for each signal that was received.
+    fn read_signals(&self, events: &mut Vec<Event>) {
+        let mut buffer = [0u8; 64];
+        loop {
+            let count = unsafe {
+                libc::read(self.read, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+            };
+            if count <= 0 {
+                return;
+            }
+            for &signum in &buffer[..count as usize] {
+                if let Some(signal) = signal_from_number(signum as libc::c_int) {
+                    events.push(Event::Signal(signal));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SignalPipe {
+    fn drop(&mut self) {
+        unsafe {
+            for &(signum, ref previous) in &self.previous_handlers {
+                libc::sigaction(signum, previous, ptr::null_mut());
+            }
+        }
+        let _ = SIGNAL_PIPE_WRITE.compare_exchange(self.write as isize, 0,
+                                                   Ordering::SeqCst, Ordering::SeqCst);
+        unsafe {
+            libc::close(self.read);
+            libc::close(self.write);
+        }
+    }
+}
+
+// Only async-signal-safe functions may be called from here.
+extern "C" fn signal_handler(signum: libc::c_int) {
+    let fd = SIGNAL_PIPE_WRITE.load(Ordering::SeqCst) as RawFd;
+    if fd != 0 {
+        // If the pipe is full the signal is dropped, but the events loop is woken up anyway.
+        let byte = signum as u8;
+        unsafe { libc::write(fd, &byte as *const u8 as *const libc::c_void, 1) };
+    }
+}
+
+fn signal_number(signal: Signal) -> libc::c_int {
+    match signal {
+        Signal::Interrupt => libc::SIGINT,
+        Signal::Terminate => libc::SIGTERM,
+        Signal::Hangup => libc::SIGHUP,
+    }
+}
+
+fn signal_from_number(signum: libc::c_int) -> Option<Signal> {
+    match signum {
+        libc::SIGINT => Some(Signal::Interrupt),
+        libc::SIGTERM => Some(Signal::Terminate),
+        libc::SIGHUP => Some(Signal::Hangup),
+        _ => None,
+    }
+}
@@ -1,16 +1,20 @@
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::fs::File;
-use std::io::Write;
-use std::os::unix::io::AsRawFd;
+use std::io::{self, Write};
+use std::mem;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::sync::{Arc, Mutex, Weak};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use {WindowEvent as Event, ElementState, MouseButton, MouseScrollDelta, TouchPhase, EventsLoopClosed, ControlFlow};
+use dpi::{LogicalPosition, LogicalSize};
 
-use super::{WindowId, DeviceId};
+use super::{WindowId, DeviceId, WaylandNotSupported};
 use super::window::WindowStore;
 use super::keyboard::init_keyboard;
+use platform::poll::EventSources;
 
 use wayland_client::{EnvHandler, EnvNotify, default_connect, EventQueue, EventQueueHandle, Proxy, StateToken};
 use wayland_client::protocol::{wl_compositor, wl_seat, wl_shell, wl_shm, wl_subcompositor,
@@ -47,6 +51,10 @@ impl EventsLoopSink {
         self.buffer.push_back(evt);
     }
 
+    fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
     fn empty_with<F>(&mut self, callback: &mut F) where F: FnMut(::Event) {
         for evt in self.buffer.drain(..) {
             callback(evt)
@@ -58,7 +66,7 @@ pub struct EventsLoop {
     // The Event Queue
     pub evq: RefCell<EventQueue>,
     // our sink, shared with some handlers, buffering the events
-    sink: Arc<Mutex<EventsLoopSink>>,
+    pub sink: Arc<Mutex<EventsLoopSink>>,
     // Whether or not there is a pending `Awakened` event to be emitted.
     pending_wakeup: Arc<AtomicBool>,
     // The window store
@@ -71,6 +79,10 @@ pub struct EventsLoop {
     pub cleanup_needed: Arc<Mutex<bool>>,
     // The wayland display
     pub display: Arc<wl_display::WlDisplay>,
+    // The other file descriptors and signals we watch
+    sources: EventSources,
+    // Whether the connection to the compositor has been lost, after which we stop dispatching
+    connection_lost: bool,
 }
 
 // A handle that can be sent across threads and used to wake up the `EventsLoop`.
@@ -104,11 +116,8 @@ impl EventsLoopProxy {
 }
 
 impl EventsLoop {
-    pub fn new() -> Option<EventsLoop> {
-        let (display, mut event_queue) = match default_connect() {
-            Ok(ret) => ret,
-            Err(_) => return None
-        };
+    pub fn new() -> Result<EventsLoop, WaylandNotSupported> {
+        let (display, mut event_queue) = default_connect()?;
 
         let registry = display.get_registry();
         let ctxt_token = event_queue.state().insert(
@@ -125,12 +134,14 @@ impl EventsLoop {
         event_queue.sync_roundtrip().expect("Wayland connection unexpectedly lost");
         event_queue.sync_roundtrip().expect("Wayland connection unexpectedly lost");
 
+        let sink = Arc::new(Mutex::new(EventsLoopSink::new()));
+
         event_queue.state().with_value(&ctxt_token, |proxy, ctxt| {
-            ctxt.ensure_shell(proxy.get_mut(&env_token))
+            ctxt.ensure_shell(proxy.get_mut(&env_token));
+            // the outputs announced from now on are reported to the user
+            ctxt.sink = Some(sink.clone());
         });
 
-        let sink = Arc::new(Mutex::new(EventsLoopSink::new()));
-
         let store = event_queue.state().insert(WindowStore::new());
 
         let seat_idata = SeatIData {
@@ -148,14 +159,16 @@ impl EventsLoop {
             store: store,
             ctxt_token: ctxt_token,
             env_token: env_token,
-            cleanup_needed: Arc::new(Mutex::new(false))
+            cleanup_needed: Arc::new(Mutex::new(false)),
+            sources: EventSources::new(),
+            connection_lost: false,
         };
 
         me.init_seat(|evqh, seat| {
             evqh.register(seat, seat_implementation(), seat_idata);
         });
 
-        Some(me)
+        Ok(me)
     }
 
     pub fn create_proxy(&self) -> EventsLoopProxy {
@@ -165,54 +178,122 @@ impl EventsLoop {
         }
     }
 
+    #[inline]
+    pub fn event_sources(&mut self) -> &mut EventSources {
+        &mut self.sources
+    }
+
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(::Event)
     {
+        if self.connection_lost {
+            return;
+        }
+
         // send pending events to the server
-        self.display.flush().expect("Wayland connection lost.");
+        if self.display.flush().is_err() {
+            return self.lose_connection(&mut callback);
+        }
 
         // dispatch any pre-buffered events
         self.sink.lock().unwrap().empty_with(&mut callback);
 
-        // try to read pending events
-        if let Some(h) = self.evq.get_mut().prepare_read() {
-            h.read_events().expect("Wayland connection lost.");
+        // dispatch the events of the other sources that are ready
+        for event in self.sources.poll() {
+            callback(event);
+        }
+
+        // read and dispatch pending wayland events
+        if self.read_and_dispatch().is_err() {
+            return self.lose_connection(&mut callback);
         }
-        // dispatch wayland events
-        self.evq.get_mut().dispatch_pending().expect("Wayland connection lost.");
         self.post_dispatch_triggers();
 
         // dispatch buffered events to client
         self.sink.lock().unwrap().empty_with(&mut callback);
     }
 
+    pub fn dispatch_ready<F>(&mut self, mut callback: F)
+        where F: FnMut(::Event)
+    {
+        self.poll_events(&mut callback);
+        // send the requests made by the callback, so that the caller can safely wait on the fd
+        if !self.connection_lost && self.display.flush().is_err() {
+            self.lose_connection(&mut callback);
+        }
+    }
+
+    #[inline]
+    pub fn get_display_fd(&self) -> RawFd {
+        unsafe { self.display.get_fd() }
+    }
+
+    pub fn poll_events_timeout<F>(&mut self, timeout: Duration, mut callback: F)
+        where F: FnMut(::Event)
+    {
+        if self.connection_lost {
+            return;
+        }
+
+        // only wait if there is nothing to deliver yet
+        if self.sink.lock().unwrap().is_empty() {
+            if self.dispatch_until(Some(Instant::now() + timeout)).is_err() {
+                return self.lose_connection(&mut callback);
+            }
+        }
+
+        self.poll_events(callback)
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(::Event) -> ControlFlow,
     {
-        // send pending events to the server
-        self.display.flush().expect("Wayland connection lost.");
+        if self.connection_lost {
+            return;
+        }
 
-        // Check for control flow by wrapping the callback.
+        // Track the control flow returned by the callback. `Break` always takes precedence.
         let control_flow = ::std::cell::Cell::new(ControlFlow::Continue);
-        let mut callback = |event| if let ControlFlow::Break = callback(event) {
-            control_flow.set(ControlFlow::Break);
+        let mut callback = |event| {
+            let flow = callback(event);
+            if control_flow.get() != ControlFlow::Break {
+                control_flow.set(flow);
+            }
         };
 
+        // send pending events to the server
+        if self.display.flush().is_err() {
+            return self.lose_connection(&mut callback);
+        }
+
         // dispatch any pre-buffered events
         self.post_dispatch_triggers();
         self.sink.lock().unwrap().empty_with(&mut callback);
 
         loop {
+            // the sink is empty, report the end of the batch before waiting
+            callback(::Event::EventsCleared);
+
+            let deadline = match control_flow.get() {
+                ControlFlow::Continue => None,
+                ControlFlow::Poll => Some(Instant::now()),
+                ControlFlow::WaitUntil(deadline) => Some(deadline),
+                ControlFlow::Break => break,
+            };
+
             // dispatch events blocking if needed
-            self.evq.get_mut().dispatch().expect("Wayland connection lost.");
+            match self.dispatch_until(deadline) {
+                Ok(true) => (),
+                Ok(false) => {
+                    callback(::Event::ResumeTimeReached);
+                    continue;
+                },
+                Err(_) => return self.lose_connection(&mut callback),
+            }
             self.post_dispatch_triggers();
 
             // empty buffer of events
             self.sink.lock().unwrap().empty_with(&mut callback);
-
-            if let ControlFlow::Break = control_flow.get() {
-                break;
-            }
         }
     }
 
@@ -253,7 +334,9 @@ pub struct StateContext {
     registry: wl_registry::WlRegistry,
     seat: Option<wl_seat::WlSeat>,
     shell: Option<Shell>,
-    monitors: Vec<Arc<Mutex<OutputInfo>>>
+    monitors: Vec<Arc<Mutex<OutputInfo>>>,
+    // where the monitor events are sent, unset while the events loop is initialized
+    sink: Option<Arc<Mutex<EventsLoopSink>>>
 }
 
 impl StateContext {
@@ -262,7 +345,17 @@ impl StateContext {
             registry: registry,
             seat: None,
             shell: None,
-            monitors: Vec::new()
+            monitors: Vec::new(),
+            sink: None
+        }
+    }
+
+    fn send_monitor_event<F>(&self, info: &Arc<Mutex<OutputInfo>>, event: F)
+        where F: FnOnce(::MonitorId) -> ::Event
+    {
+        if let Some(ref sink) = self.sink {
+            let monitor = ::MonitorId { inner: ::platform::MonitorId::Wayland(MonitorId { info: info.clone() }) };
+            sink.lock().unwrap().send_raw_event(event(monitor));
         }
     }
 
@@ -327,13 +420,59 @@ impl EventsLoop {
         }
     }
 
+    /// Dispatches the events already queued, or blocks until the server sends some new events,
+    /// one of the other sources is ready or `deadline` is reached.
+    ///
+    /// The events of the other sources are buffered in the sink. Returns `false` if the deadline
+    /// was reached first, and an error if the connection to the server was lost.
+    fn dispatch_until(&mut self, deadline: Option<Instant>) -> io::Result<bool> {
+        let evq = self.evq.get_mut();
+        // if some events are already queued, we must dispatch them rather than read
+        if let Some(guard) = evq.prepare_read() {
+            // send pending requests to the server before sleeping
+            self.display.flush()?;
+            let ready = self.sources.wait(unsafe { self.display.get_fd() }, deadline);
+            let got_source_events = !ready.events.is_empty();
+            {
+                let mut sink = self.sink.lock().unwrap();
+                for event in ready.events {
+                    sink.send_raw_event(event);
+                }
+            }
+            if !ready.display {
+                guard.cancel();
+                return Ok(got_source_events);
+            }
+            guard.read_events()?;
+        }
+        evq.dispatch_pending()?;
+        Ok(true)
+    }
+
+    // Reads the wayland events that are available without blocking, and dispatches them.
+    fn read_and_dispatch(&mut self) -> io::Result<()> {
+        let evq = self.evq.get_mut();
+        if let Some(guard) = evq.prepare_read() {
+            guard.read_events()?;
+        }
+        evq.dispatch_pending()?;
+        Ok(())
+    }
+
+    // Delivers the buffered events followed by `ConnectionLost`, after which the events loop stops
+    // dispatching.
+    fn lose_connection<F>(&mut self, callback: &mut F) where F: FnMut(::Event) {
+        self.connection_lost = true;
+        self.sink.lock().unwrap().empty_with(callback);
+        callback(::Event::ConnectionLost);
+    }
+
     fn post_dispatch_triggers(&mut self) {
         let mut sink = self.sink.lock().unwrap();
         let evq = self.evq.get_mut();
         // process a possible pending wakeup call
-        if self.pending_wakeup.load(Ordering::Relaxed) {
+        if self.pending_wakeup.swap(false, Ordering::Relaxed) {
             sink.send_raw_event(::Event::Awakened);
-            self.pending_wakeup.store(false, Ordering::Relaxed);
         }
         // prune possible dead windows
         {
@@ -345,12 +484,16 @@ impl EventsLoop {
         }
         // process pending resize/refresh
         evq.state().get_mut(&self.store).for_each(
-            |newsize, refresh, closed, wid, decorated| {
+            |newsize, size, scale, refresh, closed, wid, decorated| {
                 if let (Some((w, h)), Some(decorated)) = (newsize, decorated) {
                     decorated.resize(w as i32, h as i32);
-                    sink.send_event(::WindowEvent::Resized(w as u32, h as u32), wid);
+                    sink.send_event(::WindowEvent::Resized(LogicalSize::new(w as f64, h as f64)), wid);
                 }
                 if refresh {
+                    // the compositor doesn't tell us what was damaged, report the whole surface
+                    let (width, height) = LogicalSize::from(size).to_physical(scale).into();
+                    let damage = ::Rect { x: 0, y: 0, width: width, height: height };
+                    sink.send_event(::WindowEvent::Damaged(vec![damage]), wid);
                     sink.send_event(::WindowEvent::Refresh, wid);
                 }
                 if closed {
@@ -454,9 +597,14 @@ fn env_notify() -> EnvNotify<StateToken<StateContext>> {
         },
         del_global: |evqh, token, _, id| {
             // maybe this was a monitor, cleanup
-            evqh.state().get_mut(&token).monitors.retain(
-                |m| m.lock().unwrap().id != id
-            );
+            let ctxt = evqh.state().get_mut(&token);
+            let index = ctxt.monitors.iter().position(|m| m.lock().unwrap().id == id);
+            if let Some(index) = index {
+                let info = ctxt.monitors.remove(index);
+                if info.lock().unwrap().announced {
+                    ctxt.send_monitor_event(&info, ::Event::MonitorRemoved);
+                }
+            }
         },
         ready: |_, _, _| {}
     }
@@ -560,7 +708,7 @@ fn pointer_implementation() -> wl_pointer::Implementation<PointerIData> {
                 guard.send_event(
                     Event::MouseMoved {
                         device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
-                        position: (x, y),
+                        position: LogicalPosition::new(x, y),
                     },
                     wid,
                 );
@@ -584,7 +732,7 @@ fn pointer_implementation() -> wl_pointer::Implementation<PointerIData> {
                 idata.sink.lock().unwrap().send_event(
                     Event::MouseMoved {
                         device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
-                        position: (x, y)
+                        position: LogicalPosition::new(x, y)
                     },
                     wid
                 );
@@ -697,36 +845,85 @@ fn pointer_implementation() -> wl_pointer::Implementation<PointerIData> {
 
 fn output_impl() -> wl_output::Implementation<StateToken<StateContext>> {
     wl_output::Implementation {
-        geometry: |evqh, token, output, x, y, _, _, _, make, model, _| {
+        geometry: |evqh, token, output, x, y, mm_width, mm_height, subpixel, make, model, transform| {
             let ctxt = evqh.state().get_mut(token);
             for info in &ctxt.monitors {
                 let mut guard = info.lock().unwrap();
                 if guard.output.equals(output) {
                     guard.pix_pos = (x, y);
                     guard.name = format!("{} - {}", make, model);
+                    guard.make = make;
+                    guard.model = model;
+                    guard.mm_size = if mm_width > 0 && mm_height > 0 {
+                        Some((mm_width as u32, mm_height as u32))
+                    } else {
+                        None
+                    };
+                    guard.subpixel_order = match subpixel {
+                        wl_output::Subpixel::None => ::SubpixelOrder::None,
+                        wl_output::Subpixel::HorizontalRgb => ::SubpixelOrder::HorizontalRgb,
+                        wl_output::Subpixel::HorizontalBgr => ::SubpixelOrder::HorizontalBgr,
+                        wl_output::Subpixel::VerticalRgb => ::SubpixelOrder::VerticalRgb,
+                        wl_output::Subpixel::VerticalBgr => ::SubpixelOrder::VerticalBgr,
+                        _ => ::SubpixelOrder::Unknown,
+                    };
+                    guard.transform = match transform {
+                        wl_output::Transform::_90 => ::MonitorTransform::Rotate90,
+                        wl_output::Transform::_180 => ::MonitorTransform::Rotate180,
+                        wl_output::Transform::_270 => ::MonitorTransform::Rotate270,
+                        wl_output::Transform::Flipped => ::MonitorTransform::Flipped,
+                        wl_output::Transform::Flipped90 => ::MonitorTransform::Flipped90,
+                        wl_output::Transform::Flipped180 => ::MonitorTransform::Flipped180,
+                        wl_output::Transform::Flipped270 => ::MonitorTransform::Flipped270,
+                        _ => ::MonitorTransform::Normal,
+                    };
                     return;
                 }
             }
         },
-        mode: |evqh, token, output, flags, w, h, _refresh| {
+        mode: |evqh, token, output, flags, w, h, refresh| {
             if flags.contains(wl_output::Mode::Current) {
                 let ctxt = evqh.state().get_mut(token);
                 for info in &ctxt.monitors {
                     let mut guard = info.lock().unwrap();
                     if guard.output.equals(output) {
                         guard.pix_size = (w as u32, h as u32);
+                        // the refresh rate is given in mHz
+                        guard.refresh_rate = if refresh > 0 {
+                            Some(((refresh as f64) / 1000.0).round() as u16)
+                        } else {
+                            None
+                        };
                         return;
                     }
                 }
             }
         },
-        done: |_, _, _| {},
+        done: |evqh, token, output| {
+            // all the properties of the output have been sent
+            let ctxt = evqh.state().get_mut(token);
+            for info in &ctxt.monitors {
+                let announced = {
+                    let mut guard = info.lock().unwrap();
+                    if !guard.output.equals(output) {
+                        continue;
+                    }
+                    mem::replace(&mut guard.announced, true)
+                };
+                if announced {
+                    ctxt.send_monitor_event(info, ::Event::MonitorChanged);
+                } else {
+                    ctxt.send_monitor_event(info, ::Event::MonitorAdded);
+                }
+                return;
+            }
+        },
         scale: |evqh, token, output, scale| {
             let ctxt = evqh.state().get_mut(token);
             for info in &ctxt.monitors {
                 let mut guard = info.lock().unwrap();
                 if guard.output.equals(output) {
-                    guard.scale = scale as f32;
+                    guard.scale = scale as f64;
                     return;
                 }
             }
@@ -737,10 +934,18 @@ fn output_impl() -> wl_output::Implementation<StateToken<StateContext>> {
 pub struct OutputInfo {
     pub output: wl_output::WlOutput,
     pub id: u32,
-    pub scale: f32,
+    pub scale: f64,
     pub pix_size: (u32, u32),
     pub pix_pos: (i32, i32),
-    pub name: String
+    pub name: String,
+    pub make: String,
+    pub model: String,
+    pub mm_size: Option<(u32, u32)>,
+    pub refresh_rate: Option<u16>,
+    pub transform: ::MonitorTransform,
+    pub subpixel_order: ::SubpixelOrder,
+    // whether the output has been reported with a `done` event
+    pub announced: bool
 }
 
 impl OutputInfo {
@@ -751,7 +956,14 @@ impl OutputInfo {
             scale: 1.0,
             pix_size: (0, 0),
             pix_pos: (0, 0),
-            name: "".into()
+            name: "".into(),
+            make: "".into(),
+            model: "".into(),
+            mm_size: None,
+            refresh_rate: None,
+            transform: ::MonitorTransform::Normal,
+            subpixel_order: ::SubpixelOrder::Unknown,
+            announced: false
         }
     }
 }
@@ -780,7 +992,59 @@ impl MonitorId {
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         self.info.lock().unwrap().scale
     }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        self.info.lock().unwrap().mm_size
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.info.lock().unwrap().refresh_rate
+    }
+
+    // Wayland doesn't tell clients which parts of the outputs are covered by panels.
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> ::MonitorTransform {
+        self.info.lock().unwrap().transform
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> ::SubpixelOrder {
+        self.info.lock().unwrap().subpixel_order
+    }
+
+    // Empty until the geometry event, and some compositors send empty strings.
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        let info = self.info.lock().unwrap();
+        if info.make.is_empty() { None } else { Some(info.make.clone()) }
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        let info = self.info.lock().unwrap();
+        if info.model.is_empty() { None } else { Some(info.model.clone()) }
+    }
+
+    // wl_output doesn't give the serial number of the monitor.
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        None
+    }
+
+    // wl_output has no serial number, so identical monitors are told apart by their position in
+    // the compositor space.
+    pub fn get_stable_identifier(&self) -> String {
+        let info = self.info.lock().unwrap();
+        format!("{} {} {},{}", info.make, info.model, info.pix_pos.0, info.pix_pos.1)
+    }
 }
@@ -8,8 +8,11 @@ extern crate wayland_window;
 extern crate wayland_protocols;
 extern crate tempfile;
 
+use std::error::Error;
+use std::fmt;
+
 use wayland_client::protocol::wl_surface;
-use wayland_client::Proxy;
+use wayland_client::{ConnectError, Proxy};
 
 mod event_loop;
 mod keyboard;
@@ -21,6 +24,42 @@ pub struct DeviceId;
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(usize);
 
+/// Error returned if this system doesn't have the Wayland client library or can't connect to a
+/// Wayland compositor.
+#[derive(Clone, Debug)]
+pub enum WaylandNotSupported {
+    /// Failed to load `libwayland-client`.
+    LibraryOpenError,
+    /// No Wayland compositor could be found. Usually means that the program isn't running in a
+    /// Wayland session.
+    NoCompositorListening,
+}
+
+impl From<ConnectError> for WaylandNotSupported {
+    fn from(err: ConnectError) -> WaylandNotSupported {
+        match err {
+            ConnectError::NoWaylandLib => WaylandNotSupported::LibraryOpenError,
+            ConnectError::NoCompositorListening => WaylandNotSupported::NoCompositorListening,
+        }
+    }
+}
+
+impl Error for WaylandNotSupported {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            WaylandNotSupported::LibraryOpenError => "Failed to load the wayland client library",
+            WaylandNotSupported::NoCompositorListening => "Failed to find a wayland compositor",
+        }
+    }
+}
+
+impl fmt::Display for WaylandNotSupported {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        formatter.write_str(self.description())
+    }
+}
+
 #[inline]
 fn make_wid(s: &wl_surface::WlSurface) -> WindowId {
     WindowId(s.ptr() as usize)
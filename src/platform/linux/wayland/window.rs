@@ -1,15 +1,18 @@
+use std::cmp;
 use std::sync::{Arc, Mutex, Weak};
 
 use wayland_client::protocol::{wl_display,wl_surface};
 use wayland_client::{Proxy, StateToken};
 
 use {CreationError, MouseCursor, CursorState, WindowAttributes};
+use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
 use window::MonitorId as RootMonitorId;
 
 use super::{EventsLoop, WindowId, make_wid, MonitorId};
-use super::wayland_window::{DecoratedSurface, DecoratedSurfaceImplementation};
-use super::event_loop::StateContext;
+use super::wayland_window::{Configure, DecoratedSurface, DecoratedSurfaceImplementation};
+use super::wayland_protocols::unstable::xdg_shell::client::zxdg_toplevel_v6 as xdg_toplevel;
+use super::event_loop::{EventsLoopSink, StateContext};
 
 pub struct Window {
     surface: wl_surface::WlSurface,
@@ -17,6 +20,8 @@ pub struct Window {
     monitors: Arc<Mutex<MonitorList>>,
     ready: Arc<Mutex<bool>>,
     size: Arc<Mutex<(u32, u32)>>,
+    constraints: Arc<Mutex<SizeConstraints>>,
+    state: Arc<Mutex<::WindowState>>,
     kill_switch: (Arc<Mutex<bool>>, Arc<Mutex<bool>>),
     display: Arc<wl_display::WlDisplay>,
 }
@@ -24,32 +29,55 @@ pub struct Window {
 impl Window {
     pub fn new(evlp: &EventsLoop, attributes: &WindowAttributes) -> Result<Window, CreationError>
     {
-        let (width, height) = attributes.dimensions.unwrap_or((800,600));
+        // Wayland surfaces are sized in logical pixels.
+        let constraints = SizeConstraints {
+            min: attributes.min_dimensions.map(Into::into),
+            max: attributes.max_dimensions.map(Into::into),
+            resizable: attributes.resizable,
+        };
+        let (width, height) = constraints.clamp(attributes.dimensions.map(Into::into).unwrap_or((800,600)));
 
         // Create the decorated surface
         let ready = Arc::new(Mutex::new(false));
         let size = Arc::new(Mutex::new((width, height)));
+        // Updated by the compositor through the configure events of xdg_shell. Wayland windows
+        // can't be hidden, and the compositor doesn't tell us whether it minimized them.
+        let state = Arc::new(Mutex::new(::WindowState {
+            fullscreen: attributes.fullscreen.is_some(),
+            visible: true,
+            ..Default::default()
+        }));
         let store_token = evlp.store.clone();
         let (surface, mut decorated, xdg) = evlp.create_window(
             width, height, attributes.decorations, decorated_impl(),
             |surface| DecoratedIData {
                 ready: ready.clone(),
                 surface: surface.clone().unwrap(),
-                store_token: store_token.clone()
+                store_token: store_token.clone(),
+                state: state.clone(),
+                sink: evlp.sink.clone()
             }
         );
         // If we are using xdg, we are not ready yet
         { *ready.lock().unwrap() = !xdg; }
+        {
+            let (min, max) = constraints.limits((width, height));
+            decorated.set_min_size(min);
+            decorated.set_max_size(max);
+        }
+        let constraints = Arc::new(Mutex::new(constraints));
         // Check for fullscreen requirements
-        if let Some(RootMonitorId { inner: PlatformMonitorId::Wayland(ref monitor_id) }) = attributes.fullscreen {
-            let info = monitor_id.info.lock().unwrap();
-            decorated.set_fullscreen(Some(&info.output));
+        if let Some(ref fullscreen) = attributes.fullscreen {
+            if let RootMonitorId { inner: PlatformMonitorId::Wayland(ref monitor_id) } = *fullscreen.get_monitor() {
+                let info = monitor_id.info.lock().unwrap();
+                decorated.set_fullscreen(Some(&info.output));
+            }
         }
         // setup the monitor tracking
-        let monitor_list = Arc::new(Mutex::new(MonitorList::default()));
+        let monitor_list = Arc::new(Mutex::new(MonitorList::new()));
         {
             let mut evq = evlp.evq.borrow_mut();
-            let idata = (evlp.ctxt_token.clone(), monitor_list.clone());
+            let idata = (evlp.ctxt_token.clone(), monitor_list.clone(), evlp.sink.clone());
             evq.register(&surface, surface_impl(), idata);
         }
         // a surface commit with no buffer so that the compositor don't
@@ -64,6 +92,9 @@ impl Window {
             evq.state().get_mut(&store_token).windows.push(InternalWindow {
                 closed: false,
                 newsize: None,
+                size: size.clone(),
+                constraints: constraints.clone(),
+                monitors: monitor_list.clone(),
                 need_refresh: false,
                 surface: surface.clone().unwrap(),
                 kill_switch: kill_switch.clone(),
@@ -79,6 +110,8 @@ impl Window {
             monitors: monitor_list,
             ready: ready,
             size: size,
+            constraints: constraints,
+            state: state,
             kill_switch: (kill_switch, evlp.cleanup_needed.clone())
         })
     }
@@ -103,32 +136,84 @@ impl Window {
     }
 
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         // Not possible with wayland
         None
     }
 
     #[inline]
-    pub fn set_position(&self, _x: i32, _y: i32) {
+    pub fn set_position(&self, _position: LogicalPosition) {
         // Not possible with wayland
     }
 
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
-        Some(self.size.lock().unwrap().clone())
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
+        Some(self.size.lock().unwrap().clone().into())
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         let (w, h) = self.size.lock().unwrap().clone();
         let (w, h) = super::wayland_window::add_borders(w as i32, h as i32);
-        Some((w as u32, h as u32))
+        Some((w as u32, h as u32).into())
     }
 
     #[inline]
     // NOTE: This will only resize the borders, the contents must be updated by the user
-    pub fn set_inner_size(&self, x: u32, y: u32) {
-        self.decorated.lock().unwrap().resize(x as i32, y as i32);
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        let (x, y) = size.into();
+        let mut decorated = self.decorated.lock().unwrap();
+        decorated.resize(x as i32, y as i32);
         *(self.size.lock().unwrap()) = (x, y);
+        let constraints = self.constraints.lock().unwrap();
+        if !constraints.resizable {
+            // the limits pin the window to its size, they must follow the new one
+            let (min, max) = constraints.limits((x, y));
+            decorated.set_min_size(min);
+            decorated.set_max_size(max);
+            self.surface.commit();
+        }
+    }
+
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.constraints.lock().unwrap().min = dimensions.map(Into::into);
+        self.update_size_limits();
+    }
+
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.constraints.lock().unwrap().max = dimensions.map(Into::into);
+        self.update_size_limits();
+    }
+
+    pub fn set_resizable(&self, resizable: bool) {
+        self.constraints.lock().unwrap().resizable = resizable;
+        self.update_size_limits();
+    }
+
+    fn update_size_limits(&self) {
+        let constraints = self.constraints.lock().unwrap();
+        let mut size = self.size.lock().unwrap();
+        let mut decorated = self.decorated.lock().unwrap();
+        let clamped = constraints.clamp(*size);
+        if clamped != *size {
+            // NOTE: as with `set_inner_size`, the contents must be updated by the user
+            decorated.resize(clamped.0 as i32, clamped.1 as i32);
+            *size = clamped;
+        }
+        let (min, max) = constraints.limits(*size);
+        decorated.set_min_size(min);
+        decorated.set_max_size(max);
+        // the limits are applied on the next commit
+        self.surface.commit();
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, _minimized: bool) {
+        // TODO: wayland-window doesn't expose the `set_minimized` request of xdg_shell
+    }
+
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        *self.state.lock().unwrap()
     }
 
     #[inline]
@@ -148,18 +233,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
-        let mut factor = 1.0;
-        let guard = self.monitors.lock().unwrap();
-        for monitor_id in &guard.monitors {
-            let info = monitor_id.info.lock().unwrap();
-            if info.scale > factor { factor = info.scale; }
-        }
-        factor
+    pub fn hidpi_factor(&self) -> f64 {
+        self.monitors.lock().unwrap().scale
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, _x: i32, _y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ()> {
         // TODO: not yet possible on wayland
         Err(())
     }
@@ -195,9 +274,44 @@ impl Drop for Window {
  * Internal store for windows
  */
 
+// The size constraints requested by the user, in logical pixels.
+struct SizeConstraints {
+    min: Option<(u32, u32)>,
+    max: Option<(u32, u32)>,
+    resizable: bool,
+}
+
+impl SizeConstraints {
+    fn clamp(&self, (mut width, mut height): (u32, u32)) -> (u32, u32) {
+        if let Some((max_width, max_height)) = self.max {
+            width = cmp::min(width, max_width);
+            height = cmp::min(height, max_height);
+        }
+        if let Some((min_width, min_height)) = self.min {
+            width = cmp::max(width, min_width);
+            height = cmp::max(height, min_height);
+        }
+        (width, height)
+    }
+
+    // The limits to send to the compositor. A window that can't be resized is limited to its
+    // current size.
+    fn limits(&self, size: (u32, u32)) -> (Option<(i32, i32)>, Option<(i32, i32)>) {
+        let to_i32 = |(w, h): (u32, u32)| (w as i32, h as i32);
+        if self.resizable {
+            (self.min.map(&to_i32), self.max.map(&to_i32))
+        } else {
+            (Some(to_i32(size)), Some(to_i32(size)))
+        }
+    }
+}
+
 struct InternalWindow {
     surface: wl_surface::WlSurface,
     newsize: Option<(i32, i32)>,
+    size: Arc<Mutex<(u32, u32)>>,
+    constraints: Arc<Mutex<SizeConstraints>>,
+    monitors: Arc<Mutex<MonitorList>>,
     need_refresh: bool,
     closed: bool,
     kill_switch: Arc<Mutex<bool>>,
@@ -235,13 +349,24 @@ impl WindowStore {
     }
 
     pub fn for_each<F>(&mut self, mut f: F)
-    where F: FnMut(Option<(i32, i32)>, bool, bool, WindowId, Option<&mut DecoratedSurface>)
+    where F: FnMut(Option<(i32, i32)>, (u32, u32), f64, bool, bool, WindowId, Option<&mut DecoratedSurface>)
     {
         for window in &mut self.windows {
             let opt_arc = window.decorated.upgrade();
             let mut opt_mutex_lock = opt_arc.as_ref().map(|m| m.lock().unwrap());
+            let newsize = window.newsize.take();
+            let size = {
+                let mut size = window.size.lock().unwrap();
+                if let Some((w, h)) = newsize {
+                    *size = (w as u32, h as u32);
+                }
+                *size
+            };
+            let scale = window.monitors.lock().unwrap().scale;
             f(
-                window.newsize.take(),
+                newsize,
+                size,
+                scale,
                 window.need_refresh,
                 window.closed,
                 make_wid(&window.surface),
@@ -261,17 +386,50 @@ impl WindowStore {
 struct DecoratedIData {
     ready: Arc<Mutex<bool>>,
     store_token: StateToken<WindowStore>,
-    surface: wl_surface::WlSurface
+    surface: wl_surface::WlSurface,
+    state: Arc<Mutex<::WindowState>>,
+    sink: Arc<Mutex<EventsLoopSink>>
+}
+
+impl DecoratedIData {
+    // Called with the states of the toplevel from each configure event. zxdg_shell_v6 has no
+    // tiled states, those only exist in later versions of xdg_shell.
+    fn update_state(&self, states: &[xdg_toplevel::State]) {
+        let mut state = self.state.lock().unwrap();
+        let new_state = ::WindowState {
+            maximized: states.contains(&xdg_toplevel::State::Maximized),
+            fullscreen: states.contains(&xdg_toplevel::State::Fullscreen),
+            focused: states.contains(&xdg_toplevel::State::Activated),
+            ..*state
+        };
+        if new_state != *state {
+            *state = new_state;
+            self.sink.lock().unwrap().send_event(::WindowEvent::StateChanged(new_state), make_wid(&self.surface));
+        }
+    }
 }
 
 fn decorated_impl() -> DecoratedSurfaceImplementation<DecoratedIData> {
     DecoratedSurfaceImplementation {
-        configure: |evqh, idata, _, newsize| {
+        configure: |evqh, idata, cfg, newsize| {
             *idata.ready.lock().unwrap() = true;
+            // wl_shell doesn't tell us the state of the window
+            if let Configure::Xdg(ref states) = cfg {
+                idata.update_state(states);
+            }
             let store = evqh.state().get_mut(&idata.store_token);
             for window in &mut store.windows {
                 if window.surface.equals(&idata.surface) {
-                    window.newsize = newsize;
+                    // compositors may ignore the limits we gave them
+                    window.newsize = newsize.map(|(w, h)| {
+                        let constraints = window.constraints.lock().unwrap();
+                        let (w, h) = if constraints.resizable {
+                            constraints.clamp((w as u32, h as u32))
+                        } else {
+                            *window.size.lock().unwrap()
+                        };
+                        (w as i32, h as i32)
+                    });
                     window.need_refresh = true;
                     return;
                 }
@@ -289,24 +447,56 @@ fn decorated_impl() -> DecoratedSurfaceImplementation<DecoratedIData> {
     }
 }
 
-#[derive(Default)]
 struct MonitorList {
-    monitors: Vec<MonitorId>
+    monitors: Vec<MonitorId>,
+    // The scale of the surface, the biggest scale of the outputs it is displayed on
+    scale: f64
+}
+
+impl MonitorList {
+    fn new() -> MonitorList {
+        MonitorList {
+            monitors: Vec::new(),
+            scale: 1.0
+        }
+    }
+
+    // Called when the surface entered or left an output. If the scale of the surface changed,
+    // ask the compositor to scale our buffers accordingly and notify the user, who has to
+    // resize its buffers.
+    fn update_scale(&mut self, surface: &wl_surface::WlSurface, sink: &Mutex<EventsLoopSink>) {
+        let mut scale = 1.0;
+        for monitor_id in &self.monitors {
+            let info = monitor_id.info.lock().unwrap();
+            if info.scale > scale { scale = info.scale; }
+        }
+        if scale == self.scale {
+            return;
+        }
+        self.scale = scale;
+        // wl_surface.set_buffer_scale is only available since version 3
+        if surface.version() >= 3 {
+            surface.set_buffer_scale(scale as i32);
+        }
+        sink.lock().unwrap().send_event(::WindowEvent::HiDPIFactorChanged(scale), make_wid(surface));
+    }
 }
 
-fn surface_impl() -> wl_surface::Implementation<(StateToken<StateContext>, Arc<Mutex<MonitorList>>)> {
+fn surface_impl() -> wl_surface::Implementation<(StateToken<StateContext>, Arc<Mutex<MonitorList>>, Arc<Mutex<EventsLoopSink>>)> {
     wl_surface::Implementation {
-        enter: |evqh, &mut (ref token, ref list), _, output| {
+        enter: |evqh, &mut (ref token, ref list, ref sink), surface, output| {
             let mut guard = list.lock().unwrap();
             let ctxt = evqh.state().get(token);
             let monitor = ctxt.monitor_id_for(output);
             guard.monitors.push(monitor);
+            guard.update_scale(surface, sink);
         },
-        leave: |evqh, &mut (ref token, ref list), _, output| {
+        leave: |evqh, &mut (ref token, ref list, ref sink), surface, output| {
             let mut guard = list.lock().unwrap();
             let ctxt = evqh.state().get(token);
             let monitor = ctxt.monitor_id_for(output);
             guard.monitors.retain(|m| !Arc::ptr_eq(&m.info, &monitor.info));
+            guard.update_scale(surface, sink);
         }
     }
 }
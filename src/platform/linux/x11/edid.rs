@@ -0,0 +1,113 @@
+//! Parsing of the EDID blocks that monitors send to describe themselves.
+//!
+//! Only the identity of the monitor is extracted: see section 3.4 of the VESA E-EDID standard.
+
+const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+
+// Tags of the display descriptors of the base block.
+const DESCRIPTOR_SERIAL: u8 = 0xff;
+const DESCRIPTOR_NAME: u8 = 0xfc;
+
+/// The identity of a monitor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edid {
+    /// The three letters PNP ID of the manufacturer, for example `DEL` or `SAM`.
+    pub manufacturer: String,
+    /// The name of the model, or its product code if the monitor has no name.
+    pub model: String,
+    /// The serial number, if the manufacturer filled it.
+    pub serial: Option<String>,
+}
+
+pub fn parse(data: &[u8]) -> Option<Edid> {
+    if data.len() < 128 || data[..8] != HEADER {
+        return None;
+    }
+
+    let manufacturer = {
+        let id = (data[8] as u16) << 8 | data[9] as u16;
+        let letter = |shift: u16| (b'A' - 1 + ((id >> shift) & 0x1f) as u8) as char;
+        [letter(10), letter(5), letter(0)].iter().cloned().collect::<String>()
+    };
+    let product_code = data[10] as u16 | (data[11] as u16) << 8;
+    let serial_number = data[12] as u32 | (data[13] as u32) << 8
+                        | (data[14] as u32) << 16 | (data[15] as u32) << 24;
+
+    let mut name = None;
+    let mut serial = None;
+    for descriptor in data[54..126].chunks(18) {
+        // Display descriptors start with three zeros, detailed timings don't.
+        if descriptor[..3] != [0, 0, 0] {
+            continue;
+        }
+        match descriptor[3] {
+            DESCRIPTOR_NAME => name = descriptor_text(descriptor),
+            DESCRIPTOR_SERIAL => serial = descriptor_text(descriptor),
+            _ => (),
+        }
+    }
+
+    Some(Edid {
+        manufacturer,
+        model: name.unwrap_or_else(|| format!("{:04X}", product_code)),
+        serial: serial.or_else(|| if serial_number != 0 { Some(serial_number.to_string()) } else { None }),
+    })
+}
+
+// The text of a descriptor is terminated by a line feed and padded with spaces.
+fn descriptor_text(descriptor: &[u8]) -> Option<String> {
+    let text = &descriptor[5..];
+    let text = match text.iter().position(|&b| b == b'\n') {
+        Some(end) => &text[..end],
+        None => text,
+    };
+    let text = String::from_utf8_lossy(text).trim().to_owned();
+    if text.is_empty() { None } else { Some(text) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The EDID of a Dell U2410.
+    const DELL_U2410: [u8; 128] = [
+        0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x10, 0xac, 0x6c, 0xa0, 0x4c, 0x33, 0x41, 0x30,
+        0x2c, 0x13, 0x01, 0x03, 0x80, 0x34, 0x20, 0x78, 0xea, 0x1e, 0xc5, 0xae, 0x4f, 0x34, 0xb1, 0x26,
+        0x0e, 0x50, 0x54, 0xa5, 0x4b, 0x00, 0x81, 0x80, 0xa9, 0x40, 0xd1, 0x00, 0x71, 0x4f, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x28, 0x3c, 0x80, 0xa0, 0x70, 0xb0, 0x23, 0x40, 0x30, 0x20,
+        0x36, 0x00, 0x06, 0x44, 0x21, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00, 0xff, 0x00, 0x46, 0x35, 0x32,
+        0x35, 0x4d, 0x39, 0x42, 0x33, 0x30, 0x41, 0x33, 0x4c, 0x0a, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x44,
+        0x45, 0x4c, 0x4c, 0x20, 0x55, 0x32, 0x34, 0x31, 0x30, 0x0a, 0x20, 0x20, 0x00, 0x00, 0x00, 0xfd,
+        0x00, 0x38, 0x4c, 0x1e, 0x51, 0x11, 0x00, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x6e,
+    ];
+
+    #[test]
+    fn identity_is_read_from_the_descriptors() {
+        assert_eq!(parse(&DELL_U2410), Some(Edid {
+            manufacturer: "DEL".to_owned(),
+            model: "DELL U2410".to_owned(),
+            serial: Some("F525M9B30A3L".to_owned()),
+        }));
+    }
+
+    #[test]
+    fn codes_are_used_without_descriptors() {
+        let mut data = DELL_U2410;
+        for byte in &mut data[54..126] {
+            *byte = 0;
+        }
+        assert_eq!(parse(&data), Some(Edid {
+            manufacturer: "DEL".to_owned(),
+            model: "A06C".to_owned(),
+            serial: Some("809579340".to_owned()),
+        }));
+    }
+
+    #[test]
+    fn invalid_blocks_are_rejected() {
+        assert_eq!(parse(&DELL_U2410[..127]), None);
+        let mut data = DELL_U2410;
+        data[0] = 0xff;
+        assert_eq!(parse(&data), None);
+    }
+}
@@ -6,3 +6,7 @@ pub use x11_dl::xinput2::*;
 pub use x11_dl::xlib_xcb::*;
 pub use x11_dl::error::OpenError;
 pub use x11_dl::xrandr::*;
+pub use x11_dl::xrender::{SubPixelUnknown, SubPixelHorizontalRGB, SubPixelHorizontalBGR};
+pub use x11_dl::xrender::{SubPixelVerticalRGB, SubPixelVerticalBGR, SubPixelNone};
+// The library handle of the XTest module is misnamed in x11-dl.
+pub use x11_dl::xtest::Xf86vmode as XTest;
@@ -3,21 +3,28 @@
 pub use self::monitor::{MonitorId, get_available_monitors, get_primary_monitor};
 pub use self::window::{Window2, XWindow};
 pub use self::xdisplay::{XConnection, XNotSupported, XError};
+use self::window::get_window_state;
 
 pub mod ffi;
 
 use platform::PlatformSpecificWindowBuilderAttributes;
+use platform::poll::EventSources;
 use {CreationError, Event, EventsLoopClosed, WindowEvent, DeviceEvent,
-     KeyboardInput, ControlFlow};
+     KeyboardInput, ControlFlow, Rect};
+use dpi::{LogicalPosition, LogicalSize};
 
-use std::{mem, ptr, slice};
+use std::{io, mem, ptr, slice};
+use std::cell::Cell;
 use std::sync::{Arc, Mutex, Weak};
 use std::sync::atomic::{self, AtomicBool};
 use std::collections::HashMap;
 use std::ffi::CStr;
+use std::os::unix::io::RawFd;
+use std::time::{Duration, Instant};
 
 use libc::{self, c_uchar, c_char, c_int};
 
+mod edid;
 mod events;
 mod monitor;
 mod window;
@@ -33,6 +40,7 @@ mod xdisplay;
 pub struct EventsLoop {
     display: Arc<XConnection>,
     wm_delete_window: ffi::Atom,
+    net_wm_state: ffi::Atom,
     windows: Arc<Mutex<HashMap<WindowId, WindowData>>>,
     devices: Mutex<HashMap<DeviceId, Device>>,
     xi2ext: XExtension,
@@ -41,6 +49,15 @@ pub struct EventsLoop {
     // A dummy, `InputOnly` window that we can use to receive wakeup events and interrupt blocking
     // `XNextEvent` calls.
     wakeup_dummy_window: ffi::Window,
+    sources: EventSources,
+    // Set once we noticed that the X server closed the connection. Xlib terminates the process
+    // as soon as it notices that itself, so we must stop calling it.
+    connection_lost: Cell<bool>,
+    // The first event code of the XRandR extension, or `None` if the X server doesn't have it, in
+    // which case monitor changes aren't reported.
+    randr_event_base: Option<c_int>,
+    // The monitors as of the last XRandR notification, to find out what changed.
+    monitors: Mutex<Vec<MonitorId>>,
 }
 
 #[derive(Clone)]
@@ -50,10 +67,22 @@ pub struct EventsLoopProxy {
     wakeup_dummy_window: ffi::Window,
 }
 
+// What `EventsLoop::wait_for_input` returned for.
+enum Input {
+    // An X event can be retrieved without blocking.
+    XEvent,
+    // One of the other event sources is ready, or the deadline was reached.
+    Other,
+    // The X server closed the connection.
+    ConnectionLost,
+}
+
 impl EventsLoop {
     pub fn new(display: Arc<XConnection>) -> EventsLoop {
         let wm_delete_window = unsafe { (display.xlib.XInternAtom)(display.display, b"WM_DELETE_WINDOW\0".as_ptr() as *const c_char, 0) };
         display.check_errors().expect("Failed to call XInternAtom");
+        let net_wm_state = unsafe { (display.xlib.XInternAtom)(display.display, b"_NET_WM_STATE\0".as_ptr() as *const c_char, 0) };
+        display.check_errors().expect("Failed to call XInternAtom");
 
         let xi2ext = unsafe {
             let mut result = XExtension {
@@ -83,6 +112,20 @@ impl EventsLoop {
         }
 
         let root = unsafe { (display.xlib.XDefaultRootWindow)(display.display) };
+        monitor::check_hidpi_factor_env_var();
+
+        let randr_event_base = unsafe {
+            let (mut event_base, mut error_base) = (0, 0);
+            if (display.xrandr.XRRQueryExtension)(display.display, &mut event_base, &mut error_base) == ffi::False {
+                None
+            } else {
+                // Register for monitor hotplug and configuration change events
+                (display.xrandr.XRRSelectInput)(display.display, root, ffi::RRScreenChangeNotifyMask |
+                                                ffi::RRCrtcChangeNotifyMask | ffi::RROutputChangeNotifyMask);
+                Some(event_base)
+            }
+        };
+        let monitors = get_available_monitors(&display);
 
         let wakeup_dummy_window = unsafe {
             let (x, y, w, h) = (10, 10, 10, 10);
@@ -91,15 +134,21 @@ impl EventsLoop {
                                                border_w, border_px, background_px)
         };
 
+        display.events_loops.fetch_add(1, atomic::Ordering::SeqCst);
         let result = EventsLoop {
             pending_wakeup: Arc::new(AtomicBool::new(false)),
             display: display,
             wm_delete_window: wm_delete_window,
+            net_wm_state: net_wm_state,
             windows: Arc::new(Mutex::new(HashMap::new())),
             devices: Mutex::new(HashMap::new()),
             xi2ext: xi2ext,
             root: root,
             wakeup_dummy_window: wakeup_dummy_window,
+            sources: EventSources::new(),
+            connection_lost: Cell::new(false),
+            randr_event_base: randr_event_base,
+            monitors: Mutex::new(monitors),
         };
 
         {
@@ -135,58 +184,214 @@ impl EventsLoop {
         }
     }
 
+    #[inline]
+    pub fn event_sources(&mut self) -> &mut EventSources {
+        &mut self.sources
+    }
+
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(Event)
     {
+        if self.connection_lost.get() {
+            return;
+        }
+
+        for event in self.sources.poll() {
+            callback(event);
+        }
+
         let xlib = &self.display.xlib;
 
         let mut xev = unsafe { mem::uninitialized() };
         loop {
             // Get next event
-            unsafe {
-                // Ensure XNextEvent won't block
-                let count = (xlib.XPending)(self.display.display);
-                if count == 0 {
-                    break;
-                }
-
-                (xlib.XNextEvent)(self.display.display, &mut xev);
+            match self.pending_x_events() {
+                Some(0) => break,
+                Some(_) => unsafe { (xlib.XNextEvent)(self.display.display, &mut xev); },
+                None => {
+                    callback(Event::ConnectionLost);
+                    return;
+                },
             }
             self.process_event(&mut xev, &mut callback);
         }
     }
 
+    pub fn dispatch_ready<F>(&mut self, callback: F)
+        where F: FnMut(Event)
+    {
+        self.poll_events(callback);
+        // Send the requests made by the callback, so that the caller can safely wait on the fd.
+        if !self.connection_lost.get() {
+            unsafe { (self.display.xlib.XFlush)(self.display.display) };
+        }
+    }
+
+    #[inline]
+    pub fn get_display_fd(&self) -> RawFd {
+        unsafe { (self.display.xlib.XConnectionNumber)(self.display.display) }
+    }
+
+    pub fn poll_events_timeout<F>(&mut self, timeout: Duration, mut callback: F)
+        where F: FnMut(Event)
+    {
+        if self.connection_lost.get() {
+            return;
+        }
+
+        let mut source_events = Vec::new();
+        let input = self.wait_for_input(Some(Instant::now() + timeout), &mut source_events);
+        for event in source_events {
+            callback(event);
+        }
+        if let Input::ConnectionLost = input {
+            callback(Event::ConnectionLost);
+            return;
+        }
+        self.poll_events(callback);
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(Event) -> ControlFlow
     {
-        self.pending_wakeup.store(false, atomic::Ordering::Relaxed);
+        if self.connection_lost.get() {
+            return;
+        }
 
         let xlib = &self.display.xlib;
 
         let mut xev = unsafe { mem::uninitialized() };
+        let mut control_flow = ControlFlow::Continue;
+        let mut source_events = Vec::new();
 
         loop {
-            unsafe { (xlib.XNextEvent)(self.display.display, &mut xev) }; // Blocks as necessary
+            // Once all the queued X events have been processed, report the end of the batch
+            // before waiting. The callback may still change the way we wait.
+            if self.pending_x_events() == Some(0) {
+                let flow = callback(Event::EventsCleared);
+                if control_flow != ControlFlow::Break {
+                    control_flow = flow;
+                }
+            }
 
-            let mut control_flow = ControlFlow::Continue;
+            let deadline = match control_flow {
+                ControlFlow::Continue => None,
+                ControlFlow::Poll => Some(Instant::now()),
+                ControlFlow::WaitUntil(deadline) => Some(deadline),
+                ControlFlow::Break => break,
+            };
+            let input = self.wait_for_input(deadline, &mut source_events);
+            let timed_out = source_events.is_empty();
+            if let Input::XEvent = input {
+                unsafe { (xlib.XNextEvent)(self.display.display, &mut xev) }; // Won't block
+            }
 
-            // Track whether or not `Break` was returned when processing the event.
+            // Track the control flow returned by the callback. `Break` always takes precedence.
             {
                 let mut cb = |event| {
-                    if let ControlFlow::Break = callback(event) {
-                        control_flow = ControlFlow::Break;
+                    let flow = callback(event);
+                    if control_flow != ControlFlow::Break {
+                        control_flow = flow;
                     }
                 };
-                    
-                self.process_event(&mut xev, &mut cb);
+
+                for event in source_events.drain(..) {
+                    cb(event);
+                }
+                match input {
+                    Input::XEvent => self.process_event(&mut xev, &mut cb),
+                    Input::Other => if timed_out {
+                        cb(Event::ResumeTimeReached);
+                    },
+                    Input::ConnectionLost => {
+                        cb(Event::ConnectionLost);
+                        return;
+                    },
+                }
+            }
+        }
+    }
+
+    // The DPI factor of `window`, or 1.0 if it isn't one of our windows.
+    fn hidpi_factor(&self, window: ffi::Window) -> f64 {
+        self.windows.lock().unwrap().get(&WindowId(window)).map(|w| *w.hidpi_factor.lock().unwrap()).unwrap_or(1.0)
+    }
+
+    // Blocks until an X event is available, one of the other event sources is ready,
+    // `deadline` is reached or the connection to the X server is lost.
+    //
+    // The events of the other sources are appended to `source_events`.
+    fn wait_for_input(&self, deadline: Option<Instant>, source_events: &mut Vec<Event>) -> Input {
+        let fd = self.get_display_fd();
+        loop {
+            // The connection can become readable without any event being queued (for example
+            // when only replies were received), hence the loop.
+            let pending = match self.pending_x_events() {
+                Some(count) => count != 0,
+                None => return Input::ConnectionLost,
+            };
+            if pending {
+                // Still check the other sources, so that they can't be starved by a busy
+                // connection.
+                source_events.extend(self.sources.poll());
+                return Input::XEvent;
             }
 
-            if let ControlFlow::Break = control_flow {
-                break;
+            let ready = self.sources.wait(fd, deadline);
+            source_events.extend(ready.events);
+            if !ready.display || !source_events.is_empty() {
+                return Input::Other;
             }
         }
     }
 
+    // Returns the number of X events that can be retrieved without blocking, like `XPending`,
+    // or `None` if the connection to the X server has been lost.
+    fn pending_x_events(&self) -> Option<c_int> {
+        // `QueuedAlready` from Xlib.h, which x11-dl doesn't define
+        const QUEUED_ALREADY: c_int = 0;
+
+        let xlib = &self.display.xlib;
+
+        let queued = unsafe { (xlib.XEventsQueued)(self.display.display, QUEUED_ALREADY) };
+        if queued != 0 {
+            return Some(queued);
+        }
+
+        // `XPending` flushes the output buffer and reads the data that is already available. Xlib
+        // terminates the process if it finds out that the connection was closed while doing so,
+        // so we have to check the state of the socket ourselves first.
+        if self.connection_lost.get() || self.is_connection_closed() {
+            self.connection_lost.set(true);
+            return None;
+        }
+
+        Some(unsafe { (xlib.XPending)(self.display.display) })
+    }
+
+    // Returns `true` if the X server closed the connection.
+    fn is_connection_closed(&self) -> bool {
+        let mut byte = 0u8;
+        let ret = unsafe {
+            libc::recv(self.get_display_fd(), &mut byte as *mut u8 as *mut libc::c_void, 1,
+                       libc::MSG_PEEK | libc::MSG_DONTWAIT)
+        };
+
+        match ret {
+            0 => true,
+            ret if ret > 0 => false,
+            _ => {
+                let err = io::Error::last_os_error();
+                match err.kind() {
+                    io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => false,
+                    // The connection isn't a socket, we can't tell.
+                    _ if err.raw_os_error() == Some(libc::ENOTSOCK) => false,
+                    _ => true,
+                }
+            },
+        }
+    }
+
     fn process_event<F>(&self, xev: &mut ffi::XEvent, mut callback: F)
         where F: FnMut(Event)
     {
@@ -211,19 +416,29 @@ impl EventsLoop {
                 if client_msg.data.get_long(0) as ffi::Atom == self.wm_delete_window {
                     callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Closed })
                 } else {
-                    if self.pending_wakeup.load(atomic::Ordering::Relaxed) {
-                        self.pending_wakeup.store(false, atomic::Ordering::Relaxed);
+                    if self.pending_wakeup.swap(false, atomic::Ordering::Relaxed) {
                         callback(Event::Awakened);
                     }
                 }
             }
 
+            ffi::PropertyNotify => {
+                let xev: &ffi::XPropertyEvent = xev.as_ref();
+                if xev.atom == self.net_wm_state {
+                    self.update_window_state(xwindow, &mut callback);
+                }
+            }
+
+            ffi::MapNotify | ffi::UnmapNotify => {
+                self.update_window_state(xwindow, &mut callback);
+            }
+
             ffi::ConfigureNotify => {
                 let xev: &ffi::XConfigureEvent = xev.as_ref();
                 let size = (xev.width, xev.height);
                 let position = (xev.x, xev.y);
                 // Gymnastics to ensure self.windows isn't locked when we invoke callback
-                let (resized, moved) = {
+                let (mut resized, moved) = {
                     let mut windows = self.windows.lock().unwrap();
                     let window_data = windows.get_mut(&WindowId(xwindow)).unwrap();
                     if window_data.config.is_none() {
@@ -241,16 +456,66 @@ impl EventsLoop {
                         } else { false })
                     }
                 };
+                // The window may have been moved to a monitor with a different DPI factor. The
+                // monitors are those of the last XRandR notification, so that moving a window
+                // doesn't query the X server for all of them.
+                let hidpi_factor = if resized || moved {
+                    let old_factor = self.hidpi_factor(xwindow);
+                    let new_factor = {
+                        let monitors = self.monitors.lock().unwrap();
+                        monitor::get_monitor_for_window(&self.display, xwindow, &monitors)
+                            .map(|monitor| monitor.get_hidpi_factor())
+                            .unwrap_or(old_factor)
+                    };
+                    let changed = {
+                        let windows = self.windows.lock().unwrap();
+                        let window_data = windows.get(&WindowId(xwindow)).unwrap();
+                        *window_data.hidpi_factor.lock().unwrap() = new_factor;
+                        old_factor != new_factor
+                    };
+                    if changed {
+                        callback(Event::WindowEvent { window_id: wid, event: WindowEvent::HiDPIFactorChanged(new_factor) });
+                        // The logical size of the window changes along with the factor.
+                        resized = true;
+                    }
+                    new_factor
+                } else {
+                    self.hidpi_factor(xwindow)
+                };
                 if resized {
-                    callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Resized(xev.width as u32, xev.height as u32) });
+                    let size = LogicalSize::from_physical((xev.width as u32, xev.height as u32), hidpi_factor);
+                    callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Resized(size) });
                 }
                 if moved {
-                    callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Moved(xev.x as i32, xev.y as i32) });
+                    let position = LogicalPosition::from_physical((xev.x as i32, xev.y as i32), hidpi_factor);
+                    callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Moved(position) });
                 }
             }
 
             ffi::Expose => {
-                callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Refresh });
+                let xev: &ffi::XExposeEvent = xev.as_ref();
+                // An exposed region is described by a series of events, the last of which has a
+                // `count` of 0. Report the whole region at once.
+                let damage = {
+                    let mut windows = self.windows.lock().unwrap();
+                    windows.get_mut(&WindowId(xwindow)).and_then(|window_data| {
+                        window_data.damage.push(Rect {
+                            x: xev.x as u32,
+                            y: xev.y as u32,
+                            width: xev.width as u32,
+                            height: xev.height as u32,
+                        });
+                        if xev.count == 0 {
+                            Some(mem::replace(&mut window_data.damage, Vec::new()))
+                        } else {
+                            None
+                        }
+                    })
+                };
+                if let Some(damage) = damage {
+                    callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Damaged(damage) });
+                    callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Refresh });
+                }
             }
 
             // FIXME: Use XInput2 + libxkbcommon for keyboard input!
@@ -405,9 +670,10 @@ impl EventsLoop {
                                 true
                             } else { false }
                         } {
+                            let position = LogicalPosition::from_physical(new_cursor_pos, self.hidpi_factor(xev.event));
                             callback(Event::WindowEvent { window_id: wid, event: MouseMoved {
                                 device_id: did,
-                                position: new_cursor_pos
+                                position: position,
                             }});
                         }
 
@@ -473,7 +739,8 @@ impl EventsLoop {
                             let window_data = windows.get_mut(&WindowId(xev.event)).unwrap();
                             (self.display.xlib.XSetICFocus)(window_data.ic);
                         }
-                        callback(Event::WindowEvent { window_id: mkwid(xev.event), event: Focused(true) })
+                        callback(Event::WindowEvent { window_id: mkwid(xev.event), event: Focused(true) });
+                        self.update_window_state(xev.event, &mut callback);
                     }
                     ffi::XI_FocusOut => {
                         let xev: &ffi::XIFocusOutEvent = unsafe { &*(xev.data as *const _) };
@@ -482,7 +749,8 @@ impl EventsLoop {
                             let window_data = windows.get_mut(&WindowId(xev.event)).unwrap();
                             (self.display.xlib.XUnsetICFocus)(window_data.ic);
                         }
-                        callback(Event::WindowEvent { window_id: mkwid(xev.event), event: Focused(false) })
+                        callback(Event::WindowEvent { window_id: mkwid(xev.event), event: Focused(false) });
+                        self.update_window_state(xev.event, &mut callback);
                     }
 
                     ffi::XI_TouchBegin | ffi::XI_TouchUpdate | ffi::XI_TouchEnd => {
@@ -497,7 +765,8 @@ impl EventsLoop {
                         callback(Event::WindowEvent { window_id: wid, event: WindowEvent::Touch(Touch {
                             device_id: mkdid(xev.deviceid),
                             phase: phase,
-                            location: (xev.event_x, xev.event_y),
+                            location: LogicalPosition::from_physical((xev.event_x, xev.event_y),
+                                                                     self.hidpi_factor(xev.event)),
                             id: xev.detail as u64,
                         })})
                     }
@@ -567,10 +836,64 @@ impl EventsLoop {
                 }
             }
 
+            ty if self.randr_event_base.map(|base| base + ffi::RRScreenChangeNotify) == Some(ty) => {
+                unsafe { (self.display.xrandr.XRRUpdateConfiguration)(xev) };
+                self.update_monitors(&mut callback);
+            }
+
+            ty if self.randr_event_base.map(|base| base + ffi::RRNotify) == Some(ty) => {
+                self.update_monitors(&mut callback);
+            }
+
             _ => {}
         }
     }
 
+    // Sends a `StateChanged` event if the state of the window changed since the last one.
+    fn update_window_state<F>(&self, window: ffi::Window, callback: &mut F)
+        where F: FnMut(Event)
+    {
+        let state = get_window_state(&self.display, window);
+        {
+            let mut windows = self.windows.lock().unwrap();
+            let window_data = match windows.get_mut(&WindowId(window)) {
+                Some(window_data) => window_data,
+                None => return,
+            };
+            if window_data.state == state {
+                return;
+            }
+            window_data.state = state;
+        }
+        let window_id = ::WindowId(::platform::WindowId::X(WindowId(window)));
+        callback(Event::WindowEvent { window_id: window_id, event: WindowEvent::StateChanged(state) });
+    }
+
+    // Compares the monitors with the ones of the last XRandR notification and reports the
+    // differences. A single change usually generates several notifications, only the first one
+    // finds differences.
+    fn update_monitors<F>(&self, callback: &mut F)
+        where F: FnMut(Event)
+    {
+        let monitors = get_available_monitors(&self.display);
+        let old_monitors = mem::replace(&mut *self.monitors.lock().unwrap(), monitors.clone());
+        let root_monitor = |monitor: &MonitorId| ::MonitorId { inner: ::platform::MonitorId::X(monitor.clone()) };
+
+        // Plugging another monitor in the same connector removes the previous one.
+        for old in &old_monitors {
+            if !monitors.iter().any(|m| m.get_stable_identifier() == old.get_stable_identifier()) {
+                callback(Event::MonitorRemoved(root_monitor(old)));
+            }
+        }
+        for monitor in &monitors {
+            match old_monitors.iter().find(|m| m.get_stable_identifier() == monitor.get_stable_identifier()) {
+                None => callback(Event::MonitorAdded(root_monitor(monitor))),
+                Some(old) if old != monitor => callback(Event::MonitorChanged(root_monitor(monitor))),
+                Some(_) => (),
+            }
+        }
+    }
+
     fn init_device(&self, device: c_int) {
         let mut devices = self.devices.lock().unwrap();
         for info in DeviceInfo::get(&self.display, device).iter() {
@@ -579,6 +902,17 @@ impl EventsLoop {
     }
 }
 
+impl Drop for EventsLoop {
+    fn drop(&mut self) {
+        // The gamma ramps may have been changed through the monitors of another events loop
+        // that is still alive.
+        let last = self.display.events_loops.fetch_sub(1, atomic::Ordering::SeqCst) == 1;
+        if last && !self.connection_lost.get() {
+            monitor::restore_gamma_ramps(&self.display);
+        }
+    }
+}
+
 impl EventsLoopProxy {
     pub fn wakeup(&self) -> Result<(), EventsLoopClosed> {
         // Update the `EventsLoop`'s `pending_wakeup` flag.
@@ -717,6 +1051,9 @@ impl Window {
             config: None,
             multitouch: window.multitouch,
             cursor_pos: None,
+            damage: Vec::new(),
+            hidpi_factor: win.hidpi_factor.clone(),
+            state: win.get_state(),
         });
 
         Ok(Window {
@@ -774,6 +1111,13 @@ struct WindowData {
     ic_spot: ffi::XPoint,
     multitouch: bool,
     cursor_pos: Option<(f64, f64)>,
+    // The rectangles of the `Expose` events received so far for the current series.
+    damage: Vec<Rect>,
+    // Converts the sizes and positions reported by the X server to logical pixels. Shared with
+    // the `Window2`.
+    hidpi_factor: Arc<Mutex<f64>>,
+    // The state as of the last `StateChanged` event, to find out whether it changed.
+    state: ::WindowState,
 }
 
 // Required by ffi members
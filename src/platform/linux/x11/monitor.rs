@@ -1,9 +1,48 @@
+use std::env;
+use std::collections::HashMap;
+use std::ffi::CStr;
 use std::sync::Arc;
-use std::slice;
+use std::{cmp, mem, ptr, slice};
+use std::os::raw::{c_int, c_long, c_uchar};
 
-use super::XConnection;
+use window::{GammaRamp, MonitorTransform, SubpixelOrder, VideoMode};
 
-#[derive(Clone)]
+use super::{ffi, XConnection};
+use super::edid::{self, Edid};
+
+/// Environment variable overriding the DPI factor of every monitor on X11.
+///
+/// If it is set, it must be a positive number, or it is ignored with a warning. Otherwise the
+/// factor is computed from the `Xft.dpi` X resource if there is one, and from the physical size
+/// of each monitor reported by XRandR if there isn't.
+const HIDPI_FACTOR_ENV_VAR: &str = "WINIT_HIDPI_FACTOR";
+
+lazy_static! {
+    // Read once, when the first `EventsLoop` is created, rather than on every enumeration of the
+    // monitors.
+    static ref ENV_HIDPI_FACTOR: Option<f64> = {
+        let var = match env::var(HIDPI_FACTOR_ENV_VAR) {
+            Ok(var) => var,
+            Err(_) => return None,
+        };
+        match var.parse::<f64>() {
+            Ok(factor) if factor > 0.0 && factor.is_finite() => Some(factor),
+            _ => {
+                eprintln!("[winit] Ignoring the `{}` environment variable, which must be a positive \
+                           number, got `{}`", HIDPI_FACTOR_ENV_VAR, var);
+                None
+            },
+        }
+    };
+}
+
+/// Validates the `WINIT_HIDPI_FACTOR` environment variable, warning about a malformed value.
+#[inline]
+pub fn check_hidpi_factor_env_var() {
+    let _ = *ENV_HIDPI_FACTOR;
+}
+
+#[derive(Clone, PartialEq)]
 pub struct MonitorId {
     /// The actual id
     id: u32,
@@ -15,13 +54,221 @@ pub struct MonitorId {
     position: (i32, i32),
     /// If the monitor is the primary one
     primary: bool,
+    /// The DPI factor of the monitor
+    hidpi_factor: f64,
+    /// The first XRandR output of the monitor
+    output: ffi::RROutput,
+    /// The physical size of the monitor in millimeters, if known
+    dimensions_mm: Option<(u32, u32)>,
+    /// The refresh rate of the current mode of the monitor
+    refresh_rate: Option<u16>,
+    /// The part of the monitor not reserved by panels
+    work_area: ((i32, i32), (u32, u32)),
+    /// The rotation and reflection of the monitor
+    transform: MonitorTransform,
+    /// The subpixel order of the monitor
+    subpixel_order: SubpixelOrder,
+    /// The identity of the monitor, if its output has an EDID
+    edid: Option<Edid>,
+}
+
+/// The configuration of a CRTC, saved before switching it to another video mode.
+pub struct CrtcConfig {
+    crtc: ffi::RRCrtc,
+    mode: ffi::RRMode,
+    x: i32,
+    y: i32,
+    rotation: ffi::Rotation,
+    outputs: Vec<ffi::RROutput>,
+}
+
+// Computes the refresh rate of a mode, in hertz.
+fn mode_refresh_rate(mode: &ffi::XRRModeInfo) -> u16 {
+    let mut vtotal = mode.vTotal as f64;
+    if mode.modeFlags & ffi::RR_DoubleScan as ffi::XRRModeFlags != 0 {
+        vtotal *= 2.0;
+    }
+    if mode.modeFlags & ffi::RR_Interlace as ffi::XRRModeFlags != 0 {
+        vtotal /= 2.0;
+    }
+    if mode.hTotal == 0 || vtotal == 0.0 {
+        return 0;
+    }
+    (mode.dotClock as f64 / (mode.hTotal as f64 * vtotal)).round() as u16
+}
+
+// Finds the refresh rate of `mode` in the modes of the screen.
+unsafe fn find_refresh_rate(resources: *mut ffi::XRRScreenResources, mode: ffi::RRMode) -> Option<u16> {
+    slice::from_raw_parts((*resources).modes, (*resources).nmode as usize).iter()
+        .find(|info| info.id == mode)
+        .map(mode_refresh_rate)
+        .and_then(|rate| if rate == 0 { None } else { Some(rate) })
+}
+
+fn transform_from_rotation(rotation: ffi::Rotation) -> MonitorTransform {
+    let rotation = rotation as c_int;
+    let reflect_x = rotation & ffi::RR_Reflect_X != 0;
+    let reflect_y = rotation & ffi::RR_Reflect_Y != 0;
+    let mut quarters = match rotation & 0xf {
+        r if r == ffi::RR_Rotate_90 => 1,
+        r if r == ffi::RR_Rotate_180 => 2,
+        r if r == ffi::RR_Rotate_270 => 3,
+        _ => 0,
+    };
+    // Mirroring around the horizontal axis is mirroring around the vertical one and rotating by
+    // 180 degrees.
+    if reflect_y {
+        quarters += 2;
+    }
+    match (reflect_x != reflect_y, quarters % 4) {
+        (false, 0) => MonitorTransform::Normal,
+        (false, 1) => MonitorTransform::Rotate90,
+        (false, 2) => MonitorTransform::Rotate180,
+        (false, _) => MonitorTransform::Rotate270,
+        (true, 0) => MonitorTransform::Flipped,
+        (true, 1) => MonitorTransform::Flipped90,
+        (true, 2) => MonitorTransform::Flipped180,
+        (true, _) => MonitorTransform::Flipped270,
+    }
+}
+
+fn subpixel_order_from_xrandr(order: ffi::SubpixelOrder) -> SubpixelOrder {
+    match order as c_int {
+        ffi::SubPixelHorizontalRGB => SubpixelOrder::HorizontalRgb,
+        ffi::SubPixelHorizontalBGR => SubpixelOrder::HorizontalBgr,
+        ffi::SubPixelVerticalRGB => SubpixelOrder::VerticalRgb,
+        ffi::SubPixelVerticalBGR => SubpixelOrder::VerticalBgr,
+        ffi::SubPixelNone => SubpixelOrder::None,
+        _ => SubpixelOrder::Unknown,
+    }
+}
+
+// The work area of the current desktop, set by EWMH window managers for the whole X screen.
+fn get_desktop_work_area(x: &XConnection, root: ffi::Window) -> Option<(i64, i64, i64, i64)> {
+    unsafe {
+        let desktop = x.get_property32(root, b"_NET_CURRENT_DESKTOP\0", ffi::XA_CARDINAL)
+            .and_then(|desktop| desktop.first().cloned())
+            .unwrap_or(0) as usize;
+        x.get_property32(root, b"_NET_WORKAREA\0", ffi::XA_CARDINAL)
+            .and_then(|areas| parse_work_area(&areas, desktop))
+    }
+}
+
+// Extracts the work area of `desktop` from the value of `_NET_WORKAREA`, which has an
+// `x, y, width, height` rectangle per desktop.
+fn parse_work_area(areas: &[c_long], desktop: usize) -> Option<(i64, i64, i64, i64)> {
+    // The values are unsigned 32-bit integers.
+    let area: Vec<i64> = areas.chunks(4).nth(desktop)
+        .map(|area| area.iter().map(|&value| value as u32 as i64).collect())
+        .unwrap_or(Vec::new());
+    if area.len() != 4 {
+        return None;
+    }
+    Some((area[0], area[1], area[2], area[3]))
+}
+
+// Clips the work area of the desktop to a monitor.
+//
+// `_NET_WORKAREA` is a single rectangle for the whole screen, from which window managers remove
+// the space reserved by panels on any monitor. A panel along an edge shared by several monitors
+// therefore also shrinks the work area of the monitors it isn't on.
+fn clip_work_area(work_area: Option<(i64, i64, i64, i64)>, (mx, my): (i32, i32), (mw, mh): (u32, u32))
+    -> ((i32, i32), (u32, u32))
+{
+    let monitor = ((mx, my), (mw, mh));
+    let (wx, wy, ww, wh) = match work_area {
+        Some(area) => area,
+        None => return monitor,
+    };
+    let (mx, my, mw, mh) = (mx as i64, my as i64, mw as i64, mh as i64);
+    let left = cmp::max(wx, mx);
+    let top = cmp::max(wy, my);
+    let right = cmp::min(wx + ww, mx + mw);
+    let bottom = cmp::min(wy + wh, my + mh);
+    if right <= left || bottom <= top {
+        // The work area doesn't cover the monitor, it is probably out of date.
+        return monitor;
+    }
+    ((left as i32, top as i32), ((right - left) as u32, (bottom - top) as u32))
+}
+
+// Reads and parses the `EDID` property of an output.
+unsafe fn get_output_edid(x: &XConnection, output: ffi::RROutput) -> Option<Edid> {
+    let atom = (x.xlib.XInternAtom)(x.display, b"EDID\0".as_ptr() as *const _, 1);
+    if atom == 0 {
+        return None;
+    }
+    let (mut actual_type, mut actual_format, mut nitems, mut bytes_after) = (0, 0, 0, 0);
+    let mut data: *mut c_uchar = ptr::null_mut();
+    // The base block and its extensions are 128 bytes each, the identity is in the base block.
+    let status = (x.xrandr.XRRGetOutputProperty)(x.display, output, atom, 0, 128 / 4, 0, 0,
+                                                 ffi::XA_INTEGER, &mut actual_type, &mut actual_format,
+                                                 &mut nitems, &mut bytes_after, &mut data);
+    if status != ffi::Success as c_int || data.is_null() {
+        return None;
+    }
+    let edid = if actual_type == ffi::XA_INTEGER && actual_format == 8 {
+        edid::parse(slice::from_raw_parts(data, nitems as usize))
+    } else {
+        None
+    };
+    (x.xlib.XFree)(data as *mut _);
+    edid
+}
+
+// The DPI factor forced by the user for all monitors, if any.
+fn hidpi_factor_override(x: &XConnection) -> Option<f64> {
+    if let Some(factor) = *ENV_HIDPI_FACTOR {
+        return Some(factor);
+    }
+
+    let resources = unsafe { (x.xlib.XResourceManagerString)(x.display) };
+    if resources.is_null() {
+        return None;
+    }
+    let resources = unsafe { CStr::from_ptr(resources) }.to_string_lossy();
+    resources.lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if name.trim() == "Xft.dpi" => value.trim().parse::<f64>().ok(),
+                _ => None,
+            }
+        })
+        .find(|dpi| *dpi > 0.0)
+        .map(|dpi| dpi / 96.0)
+}
+
+// Outputs such as projectors or virtual displays have no physical size.
+fn physical_dimensions_mm((width, height): (i64, i64)) -> Option<(u32, u32)> {
+    if width <= 0 || height <= 0 {
+        None
+    } else {
+        Some((width as u32, height as u32))
+    }
+}
+
+unsafe fn output_dimensions_mm(output: *mut ffi::XRROutputInfo) -> Option<(u32, u32)> {
+    physical_dimensions_mm(((*output).mm_width as i64, (*output).mm_height as i64))
+}
+
+// Computes the DPI factor of a monitor from its size in pixels and in millimeters.
+fn calc_hidpi_factor((width_px, height_px): (u32, u32), (width_mm, height_mm): (u64, u64)) -> f64 {
+    // Outputs such as projectors or virtual displays have no physical size.
+    if width_mm == 0 || height_mm == 0 {
+        return 1.0;
+    }
+    let ppmm = ((width_px as f64 * height_px as f64) / (width_mm as f64 * height_mm as f64)).sqrt();
+    ((ppmm * (12.0 * 25.4 / 96.0)).round() / 12.0).max(1.0) // quantize with 1/12 step size.
 }
 
 pub fn get_available_monitors(x: &Arc<XConnection>) -> Vec<MonitorId> {
     let mut available = Vec::new();
+    let hidpi_override = hidpi_factor_override(x);
     unsafe {
         let root = (x.xlib.XDefaultRootWindow)(x.display);
-        let resources = (x.xrandr.XRRGetScreenResources)(x.display, root);
+        let resources = (x.xrandr.XRRGetScreenResourcesCurrent)(x.display, root);
+        let desktop_work_area = get_desktop_work_area(x, root);
 
         let mut major = 0;
         let mut minor = 0;
@@ -32,16 +279,46 @@ pub fn get_available_monitors(x: &Arc<XConnection>) -> Vec<MonitorId> {
             let monitors = (x.xrandr.XRRGetMonitors)(x.display, root, 1, &mut nmonitors);
             for i in 0..nmonitors {
                 let monitor = *(monitors.offset(i as isize));
-                let output = (x.xrandr.XRRGetOutputInfo)(x.display, resources, *(monitor.outputs.offset(0)));
+                let output_id = *(monitor.outputs.offset(0));
+                let output = (x.xrandr.XRRGetOutputInfo)(x.display, resources, output_id);
                 let nameslice = slice::from_raw_parts((*output).name as *mut u8, (*output).nameLen as usize);
                 let name = String::from_utf8_lossy(nameslice).into_owned();
+                let dimensions = (monitor.width as u32, monitor.height as u32);
+                let hidpi_factor = hidpi_override.unwrap_or_else(|| {
+                    calc_hidpi_factor(dimensions, ((*output).mm_width as u64, (*output).mm_height as u64))
+                });
+                let position = (monitor.x as i32, monitor.y as i32);
+                let (refresh_rate, transform) = if (*output).crtc != 0 {
+                    let crtc = (x.xrandr.XRRGetCrtcInfo)(x.display, resources, (*output).crtc);
+                    let properties = (find_refresh_rate(resources, (*crtc).mode),
+                                      transform_from_rotation((*crtc).rotation));
+                    (x.xrandr.XRRFreeCrtcInfo)(crtc);
+                    properties
+                } else {
+                    (None, MonitorTransform::Normal)
+                };
+                // The size of a monitor grouping several outputs isn't the size of its first one.
+                let dimensions_mm = if monitor.noutput == 1 {
+                    output_dimensions_mm(output)
+                } else {
+                    physical_dimensions_mm((monitor.mwidth as i64, monitor.mheight as i64))
+                };
+                let subpixel_order = subpixel_order_from_xrandr((*output).subpixel_order);
                 (x.xrandr.XRRFreeOutputInfo)(output);
                 available.push(MonitorId{
                     id: i as u32,
                     name,
-                    dimensions: (monitor.width as u32, monitor.height as u32),
-                    position: (monitor.x as i32, monitor.y as i32),
+                    dimensions,
+                    position,
                     primary: (monitor.primary != 0),
+                    hidpi_factor,
+                    output: output_id,
+                    dimensions_mm,
+                    refresh_rate,
+                    work_area: clip_work_area(desktop_work_area, position, dimensions),
+                    transform,
+                    subpixel_order,
+                    edid: get_output_edid(x, output_id),
                 });
             }
             (x.xrandr.XRRFreeMonitors)(monitors);
@@ -53,16 +330,32 @@ pub fn get_available_monitors(x: &Arc<XConnection>) -> Vec<MonitorId> {
                 let crtcid = *((*resources).crtcs.offset(i as isize));
                 let crtc = (x.xrandr.XRRGetCrtcInfo)(x.display, resources, crtcid);
                 if (*crtc).width > 0 && (*crtc).height > 0 && (*crtc).noutput > 0 {
-                    let output = (x.xrandr.XRRGetOutputInfo)(x.display, resources, *((*crtc).outputs.offset(0)));
+                    let output_id = *((*crtc).outputs.offset(0));
+                    let output = (x.xrandr.XRRGetOutputInfo)(x.display, resources, output_id);
                     let nameslice = slice::from_raw_parts((*output).name as *mut u8, (*output).nameLen as usize);
                     let name = String::from_utf8_lossy(nameslice).into_owned();
+                    let dimensions = ((*crtc).width as u32, (*crtc).height as u32);
+                    let hidpi_factor = hidpi_override.unwrap_or_else(|| {
+                        calc_hidpi_factor(dimensions, ((*output).mm_width as u64, (*output).mm_height as u64))
+                    });
+                    let position = ((*crtc).x as i32, (*crtc).y as i32);
+                    let dimensions_mm = output_dimensions_mm(output);
+                    let subpixel_order = subpixel_order_from_xrandr((*output).subpixel_order);
                     (x.xrandr.XRRFreeOutputInfo)(output);
                     available.push(MonitorId{
                         id: crtcid as u32,
                         name,
-                        dimensions: ((*crtc).width as u32, (*crtc).height as u32),
-                        position: ((*crtc).x as i32, (*crtc).y as i32),
+                        dimensions,
+                        position,
                         primary: true,
+                        hidpi_factor,
+                        output: output_id,
+                        dimensions_mm,
+                        refresh_rate: find_refresh_rate(resources, (*crtc).mode),
+                        work_area: clip_work_area(desktop_work_area, position, dimensions),
+                        transform: transform_from_rotation((*crtc).rotation),
+                        subpixel_order,
+                        edid: get_output_edid(x, output_id),
                     });
                 }
                 (x.xrandr.XRRFreeCrtcInfo)(crtc);
@@ -75,12 +368,64 @@ pub fn get_available_monitors(x: &Arc<XConnection>) -> Vec<MonitorId> {
 
 #[inline]
 pub fn get_primary_monitor(x: &Arc<XConnection>) -> MonitorId {
-    get_available_monitors(x).into_iter().find(|m| m.primary)
-        // 'no primary' case is better handled picking some existing monitor
-        .or_else(|| get_available_monitors(x).into_iter().next())
+    find_primary_monitor(&get_available_monitors(x))
         .expect("[winit] Failed to find any x11 monitor")
 }
 
+/// Returns the primary monitor of `monitors`, or the first one if none is primary.
+pub fn find_primary_monitor(monitors: &[MonitorId]) -> Option<MonitorId> {
+    monitors.iter().find(|m| m.primary)
+        // 'no primary' case is better handled picking some existing monitor
+        .or_else(|| monitors.first())
+        .cloned()
+}
+
+/// Returns the monitor of `monitors` that overlaps the most with `window`, or the first one if
+/// the window doesn't overlap any of them. Returns `None` if `monitors` is empty.
+pub fn get_monitor_for_window(x: &Arc<XConnection>, window: ffi::Window, monitors: &[MonitorId])
+                              -> Option<MonitorId>
+{
+    let default = match monitors.first() {
+        Some(monitor) => monitor.clone(),
+        None => return None,
+    };
+
+    // The position reported by `XGetGeometry` is relative to the parent, which is the frame of
+    // the window manager if there is one.
+    let (wx, wy, ww, wh) = unsafe {
+        let (mut root, mut wx, mut wy, mut ww, mut wh, mut border, mut depth) = (0, 0, 0, 0, 0, 0, 0);
+        if (x.xlib.XGetGeometry)(x.display, window, &mut root, &mut wx, &mut wy, &mut ww, &mut wh,
+                                 &mut border, &mut depth) == 0
+        {
+            return Some(default);
+        }
+        let mut child = 0;
+        if (x.xlib.XTranslateCoordinates)(x.display, window, root, 0, 0, &mut wx, &mut wy, &mut child) == 0 {
+            return Some(default);
+        }
+        (wx as i64, wy as i64, ww as i64, wh as i64)
+    };
+
+    // Find the monitor with the biggest overlap with the window
+    let mut overlap = 0;
+    let mut find = default;
+    for monitor in monitors {
+        let (mx, my) = monitor.position;
+        let (mw, mh) = monitor.dimensions;
+        let (mx, my, mw, mh) = (mx as i64, my as i64, mw as i64, mh as i64);
+        let width = cmp::min(wx + ww, mx + mw) - cmp::max(wx, mx);
+        let height = cmp::min(wy + wh, my + mh) - cmp::max(wy, my);
+        let osize = if width <= 0 || height <= 0 { 0 } else { width * height };
+
+        if osize > overlap {
+            overlap = osize;
+            find = monitor.clone();
+        }
+    }
+
+    Some(find)
+}
+
 impl MonitorId {
     pub fn get_name(&self) -> Option<String> {
         Some(self.name.clone())
@@ -100,7 +445,263 @@ impl MonitorId {
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
-        1.0
+    pub fn get_hidpi_factor(&self) -> f64 {
+        self.hidpi_factor
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        self.dimensions_mm
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.refresh_rate
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        Some(self.work_area)
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        self.transform
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> SubpixelOrder {
+        self.subpixel_order
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        self.edid.as_ref().map(|edid| edid.manufacturer.clone())
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        self.edid.as_ref().map(|edid| edid.model.clone())
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        self.edid.as_ref().and_then(|edid| edid.serial.clone())
+    }
+
+    pub fn get_stable_identifier(&self) -> String {
+        match self.edid {
+            Some(Edid { ref manufacturer, ref model, serial: Some(ref serial) }) => {
+                format!("{} {} {}", manufacturer, model, serial)
+            },
+            // Identical monitors without serial numbers are told apart by their connector.
+            Some(Edid { ref manufacturer, ref model, serial: None }) => {
+                format!("{} {} {}", manufacturer, model, self.name)
+            },
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn get_video_modes(&self, x: &Arc<XConnection>) -> Vec<VideoMode> {
+        let mut modes = Vec::new();
+        unsafe {
+            let root = (x.xlib.XDefaultRootWindow)(x.display);
+            let bit_depth = (x.xlib.XDefaultDepth)(x.display, (x.xlib.XDefaultScreen)(x.display)) as u16;
+            let resources = (x.xrandr.XRRGetScreenResourcesCurrent)(x.display, root);
+            let output = (x.xrandr.XRRGetOutputInfo)(x.display, resources, self.output);
+            if !output.is_null() {
+                // The output only lists the identifiers of its modes, they are described in the
+                // screen resources.
+                let mode_infos = slice::from_raw_parts((*resources).modes, (*resources).nmode as usize);
+                for &mode in slice::from_raw_parts((*output).modes, (*output).nmode as usize) {
+                    if let Some(info) = mode_infos.iter().find(|info| info.id == mode) {
+                        modes.push(VideoMode {
+                            dimensions: (info.width as u32, info.height as u32),
+                            refresh_rate: mode_refresh_rate(info),
+                            bit_depth: bit_depth,
+                            monitor: ::MonitorId { inner: ::platform::MonitorId::X(self.clone()) },
+                            native_mode: mode as u64,
+                        });
+                    }
+                }
+                (x.xrandr.XRRFreeOutputInfo)(output);
+            }
+            (x.xrandr.XRRFreeScreenResources)(resources);
+        }
+        modes
+    }
+
+    // The CRTC currently displaying the monitor, if it is enabled.
+    fn get_crtc(&self, x: &XConnection) -> Option<ffi::RRCrtc> {
+        unsafe {
+            let root = (x.xlib.XDefaultRootWindow)(x.display);
+            let resources = (x.xrandr.XRRGetScreenResourcesCurrent)(x.display, root);
+            let output = (x.xrandr.XRRGetOutputInfo)(x.display, resources, self.output);
+            let crtc = if output.is_null() { 0 } else { (*output).crtc };
+            if !output.is_null() {
+                (x.xrandr.XRRFreeOutputInfo)(output);
+            }
+            (x.xrandr.XRRFreeScreenResources)(resources);
+            if crtc == 0 { None } else { Some(crtc) }
+        }
+    }
+
+    pub fn get_gamma_ramp(&self, x: &Arc<XConnection>) -> Option<GammaRamp> {
+        self.get_crtc(x).and_then(|crtc| unsafe { get_crtc_gamma_ramp(x, crtc) })
+    }
+
+    pub fn set_gamma_ramp(&self, x: &Arc<XConnection>, ramp: &GammaRamp) -> Result<(), ()> {
+        let crtc = match self.get_crtc(x) {
+            Some(crtc) => crtc,
+            None => return Err(()),
+        };
+        {
+            let mut saved = x.saved_gamma_ramps.lock().unwrap();
+            if !saved.contains_key(&crtc) {
+                match unsafe { get_crtc_gamma_ramp(x, crtc) } {
+                    Some(original) => { saved.insert(crtc, original); },
+                    None => return Err(()),
+                }
+            }
+        }
+        unsafe { set_crtc_gamma_ramp(x, crtc, ramp) }
+    }
+
+    /// Switches the CRTC displaying the monitor to `mode`, and returns its previous configuration.
+    pub fn set_video_mode(&self, x: &Arc<XConnection>, mode: ffi::RRMode) -> Result<CrtcConfig, ()> {
+        // The output is disconnected or disabled
+        let crtc_id = match self.get_crtc(x) {
+            Some(crtc) => crtc,
+            None => return Err(()),
+        };
+        unsafe {
+            let root = (x.xlib.XDefaultRootWindow)(x.display);
+            let resources = (x.xrandr.XRRGetScreenResourcesCurrent)(x.display, root);
+
+            let crtc = (x.xrandr.XRRGetCrtcInfo)(x.display, resources, crtc_id);
+            let previous = CrtcConfig {
+                crtc: crtc_id,
+                mode: (*crtc).mode,
+                x: (*crtc).x as i32,
+                y: (*crtc).y as i32,
+                rotation: (*crtc).rotation,
+                outputs: slice::from_raw_parts((*crtc).outputs, (*crtc).noutput as usize).to_vec(),
+            };
+            let status = (x.xrandr.XRRSetCrtcConfig)(x.display, resources, crtc_id, ffi::CurrentTime,
+                                                     (*crtc).x, (*crtc).y, mode, (*crtc).rotation,
+                                                     (*crtc).outputs, (*crtc).noutput);
+            (x.xrandr.XRRFreeCrtcInfo)(crtc);
+            (x.xrandr.XRRFreeScreenResources)(resources);
+
+            if x.check_errors().is_err() || status != ffi::Success as c_int {
+                return Err(());
+            }
+            Ok(previous)
+        }
+    }
+}
+
+unsafe fn get_crtc_gamma_ramp(x: &XConnection, crtc: ffi::RRCrtc) -> Option<GammaRamp> {
+    let gamma = (x.xrandr.XRRGetCrtcGamma)(x.display, crtc);
+    if gamma.is_null() {
+        return None;
+    }
+    let size = (*gamma).size as usize;
+    let ramp = GammaRamp {
+        red: slice::from_raw_parts((*gamma).red, size).to_vec(),
+        green: slice::from_raw_parts((*gamma).green, size).to_vec(),
+        blue: slice::from_raw_parts((*gamma).blue, size).to_vec(),
+    };
+    (x.xrandr.XRRFreeGamma)(gamma);
+    if size == 0 { None } else { Some(ramp) }
+}
+
+unsafe fn set_crtc_gamma_ramp(x: &XConnection, crtc: ffi::RRCrtc, ramp: &GammaRamp) -> Result<(), ()> {
+    let size = (x.xrandr.XRRGetCrtcGammaSize)(x.display, crtc) as usize;
+    if size == 0 || ramp.red.len() != size || ramp.green.len() != size || ramp.blue.len() != size {
+        return Err(());
+    }
+    let gamma = (x.xrandr.XRRAllocGamma)(size as c_int);
+    if gamma.is_null() {
+        return Err(());
+    }
+    slice::from_raw_parts_mut((*gamma).red, size).copy_from_slice(&ramp.red);
+    slice::from_raw_parts_mut((*gamma).green, size).copy_from_slice(&ramp.green);
+    slice::from_raw_parts_mut((*gamma).blue, size).copy_from_slice(&ramp.blue);
+    (x.xrandr.XRRSetCrtcGamma)(x.display, crtc, gamma);
+    (x.xrandr.XRRFreeGamma)(gamma);
+    // The request is asynchronous, wait for the server to process it so that its errors are caught.
+    (x.xlib.XSync)(x.display, ffi::False);
+    x.check_errors().map_err(|_| ())
+}
+
+/// Restores the gamma ramps of all the CRTCs changed by `MonitorId::set_gamma_ramp`.
+pub fn restore_gamma_ramps(x: &XConnection) {
+    let saved = mem::replace(&mut *x.saved_gamma_ramps.lock().unwrap(), HashMap::new());
+    for (crtc, ramp) in saved {
+        // The CRTC may have been disabled since.
+        let _ = unsafe { set_crtc_gamma_ramp(x, crtc, &ramp) };
+    }
+}
+
+/// Restores a CRTC configuration saved by `MonitorId::set_video_mode`.
+pub fn restore_crtc_config(x: &Arc<XConnection>, config: &CrtcConfig) {
+    unsafe {
+        let root = (x.xlib.XDefaultRootWindow)(x.display);
+        let resources = (x.xrandr.XRRGetScreenResourcesCurrent)(x.display, root);
+        let mut outputs = config.outputs.clone();
+        (x.xrandr.XRRSetCrtcConfig)(x.display, resources, config.crtc, ffi::CurrentTime,
+                                    config.x, config.y, config.mode, config.rotation,
+                                    outputs.as_mut_ptr(), outputs.len() as c_int);
+        (x.xrandr.XRRFreeScreenResources)(resources);
+    }
+    // The CRTC may have been reconfigured since, by another client or because the monitor
+    // was unplugged.
+    let _ = x.check_errors();
+}
+
+#[cfg(test)]
+mod tests {
+    use window::MonitorTransform;
+    use super::{clip_work_area, ffi, parse_work_area, transform_from_rotation};
+
+    #[test]
+    fn transforms_combine_rotations_and_reflections() {
+        let transform = |rotation: i32| transform_from_rotation(rotation as ffi::Rotation);
+        assert_eq!(transform(ffi::RR_Rotate_0), MonitorTransform::Normal);
+        assert_eq!(transform(ffi::RR_Rotate_90), MonitorTransform::Rotate90);
+        assert_eq!(transform(ffi::RR_Rotate_180), MonitorTransform::Rotate180);
+        assert_eq!(transform(ffi::RR_Rotate_270), MonitorTransform::Rotate270);
+        assert_eq!(transform(ffi::RR_Rotate_0 | ffi::RR_Reflect_X), MonitorTransform::Flipped);
+        assert_eq!(transform(ffi::RR_Rotate_90 | ffi::RR_Reflect_X), MonitorTransform::Flipped90);
+        // Mirroring vertically is mirroring horizontally and rotating by 180 degrees.
+        assert_eq!(transform(ffi::RR_Rotate_0 | ffi::RR_Reflect_Y), MonitorTransform::Flipped180);
+        assert_eq!(transform(ffi::RR_Rotate_90 | ffi::RR_Reflect_Y), MonitorTransform::Flipped270);
+        // Mirroring both ways is a rotation.
+        assert_eq!(transform(ffi::RR_Rotate_90 | ffi::RR_Reflect_X | ffi::RR_Reflect_Y),
+                   MonitorTransform::Rotate270);
+    }
+
+    #[test]
+    fn work_area_of_the_current_desktop_is_parsed() {
+        let areas = [0, 24, 1920, 1056, 0, 0, 1920, 1080];
+        assert_eq!(parse_work_area(&areas, 0), Some((0, 24, 1920, 1056)));
+        assert_eq!(parse_work_area(&areas, 1), Some((0, 0, 1920, 1080)));
+        assert_eq!(parse_work_area(&areas, 2), None);
+        assert_eq!(parse_work_area(&areas[..6], 1), None);
+        // The values are unsigned.
+        assert_eq!(parse_work_area(&[0, 0, 0xffffffff, 1080], 0), Some((0, 0, 0xffffffff, 1080)));
+    }
+
+    #[test]
+    fn work_area_is_clipped_to_the_monitor() {
+        // A panel at the top of a screen made of two monitors side by side.
+        let work_area = Some((0, 24, 3840, 1056));
+        assert_eq!(clip_work_area(work_area, (0, 0), (1920, 1080)), ((0, 24), (1920, 1056)));
+        assert_eq!(clip_work_area(work_area, (1920, 0), (1920, 1080)), ((1920, 24), (1920, 1056)));
+        // Without a work area, or with one that doesn't cover the monitor, the whole monitor is used.
+        assert_eq!(clip_work_area(None, (0, 0), (1920, 1080)), ((0, 0), (1920, 1080)));
+        assert_eq!(clip_work_area(Some((0, 0, 1920, 1080)), (1920, 0), (1920, 1080)),
+                   ((1920, 0), (1920, 1080)));
     }
 }
@@ -10,14 +10,17 @@ use std::thread;
 use std::time::Duration;
 
 use CursorState;
+use Fullscreen;
 use WindowAttributes;
+use dpi::{LogicalPosition, LogicalSize};
 use platform::PlatformSpecificWindowBuilderAttributes;
 
 use platform::MonitorId as PlatformMonitorId;
 use platform::x11::MonitorId as X11MonitorId;
 use window::MonitorId as RootMonitorId;
 
-use platform::x11::monitor::get_available_monitors;
+use platform::x11::monitor::{CrtcConfig, find_primary_monitor, get_available_monitors, get_monitor_for_window,
+                             get_primary_monitor, restore_crtc_config};
 
 use super::{ffi};
 use super::{XConnection, WindowId, EventsLoop};
@@ -29,6 +32,112 @@ fn with_c_str<F, T>(s: &str, f: F) -> T where F: FnOnce(*const libc::c_char) ->
     f(c_str.as_ptr())
 }
 
+/// Reads the state of `window` from the X server. The maximized, minimized and fullscreen states
+/// are set by the window manager in the `_NET_WM_STATE` property.
+pub fn get_window_state(display: &XConnection, window: ffi::Window) -> ::WindowState {
+    unsafe {
+        let intern = |name: &[u8]| (display.xlib.XInternAtom)(display.display, name.as_ptr() as *const _, 1);
+        let atoms: Vec<ffi::Atom> = display.get_property32(window, b"_NET_WM_STATE\0", ffi::XA_ATOM)
+            .unwrap_or(Vec::new())
+            .into_iter()
+            .map(|atom| atom as ffi::Atom)
+            .collect();
+        let has_state = |name: &[u8]| {
+            let atom = intern(name);
+            atom != 0 && atoms.contains(&atom)
+        };
+
+        let mut focus: ffi::Window = 0;
+        let mut revert_to: c_int = 0;
+        (display.xlib.XGetInputFocus)(display.display, &mut focus, &mut revert_to);
+
+        let mut attributes: ffi::XWindowAttributes = mem::zeroed();
+        (display.xlib.XGetWindowAttributes)(display.display, window, &mut attributes);
+        display.ignore_error();
+
+        let minimized = has_state(b"_NET_WM_STATE_HIDDEN\0");
+        ::WindowState {
+            maximized: has_state(b"_NET_WM_STATE_MAXIMIZED_VERT\0")
+                       && has_state(b"_NET_WM_STATE_MAXIMIZED_HORZ\0"),
+            minimized: minimized,
+            fullscreen: has_state(b"_NET_WM_STATE_FULLSCREEN\0"),
+            focused: focus == window,
+            // Window managers unmap the windows they minimize.
+            visible: attributes.map_state == ffi::IsViewable || minimized,
+        }
+    }
+}
+
+fn intern_atom(display: &XConnection, name: &str) -> ffi::Atom {
+    let atom = unsafe {
+        with_c_str(name, |name| (display.xlib.XInternAtom)(display.display, name, 0))
+    };
+    display.check_errors().expect("Failed to call XInternAtom");
+    atom
+}
+
+/// Returns `true` if `window` isn't managed by the window manager, because it has never been
+/// mapped or because it has been unmapped by the application. The window manager sets the
+/// `WM_STATE` property of the windows it manages.
+fn is_withdrawn(display: &XConnection, window: ffi::Window) -> bool {
+    let wm_state = intern_atom(display, "WM_STATE");
+    let state = unsafe { display.get_property32(window, b"WM_STATE\0", wm_state) };
+    display.ignore_error();
+    match state.as_ref().and_then(|state| state.first()) {
+        // `0` is `WithdrawnState`
+        Some(&state) => state == 0,
+        None => true,
+    }
+}
+
+/// Returns the `_NET_WM_STATE` atoms a new window starts with.
+fn initial_netwm_states(window_attrs: &WindowAttributes,
+                        pl_attribs: &PlatformSpecificWindowBuilderAttributes) -> Vec<&'static str>
+{
+    let mut states = Vec::new();
+    if window_attrs.maximized {
+        states.push("_NET_WM_STATE_MAXIMIZED_HORZ");
+        states.push("_NET_WM_STATE_MAXIMIZED_VERT");
+    }
+    if window_attrs.always_on_top {
+        states.push("_NET_WM_STATE_ABOVE");
+    }
+    if pl_attribs.sticky {
+        states.push("_NET_WM_STATE_STICKY");
+    }
+    if pl_attribs.skip_taskbar {
+        states.push("_NET_WM_STATE_SKIP_TASKBAR");
+    }
+    if pl_attribs.skip_pager {
+        states.push("_NET_WM_STATE_SKIP_PAGER");
+    }
+    states
+}
+
+/// Adds `atom` to or removes it from the `_NET_WM_STATE` atoms `atoms`.
+fn toggle_netwm_state(atoms: &[ffi::Atom], atom: ffi::Atom, val: bool) -> Vec<ffi::Atom> {
+    let mut atoms: Vec<ffi::Atom> = atoms.iter().cloned().filter(|&other| other != atom).collect();
+    if val {
+        atoms.push(atom);
+    }
+    atoms
+}
+
+/// Replaces the `_NET_WM_STATE` property of `window` with `atoms`.
+///
+/// Window managers ignore the `_NET_WM_STATE` client messages about windows they don't manage,
+/// and read this property instead when they start managing them.
+fn set_netwm_state_property(display: &XConnection, window: ffi::Window, atoms: &[ffi::Atom]) {
+    let net_wm_state = intern_atom(display, "_NET_WM_STATE");
+    unsafe {
+        (display.xlib.XChangeProperty)(display.display, window, net_wm_state, ffi::XA_ATOM, 32,
+                                       ffi::PropModeReplace, atoms.as_ptr() as *const u8,
+                                       atoms.len() as c_int);
+        (display.xlib.XFlush)(display.display);
+    }
+    display.check_errors().expect("Failed to set the _NET_WM_STATE property");
+}
+
 pub struct XWindow {
     display: Arc<XConnection>,
     window: ffi::Window,
@@ -42,9 +151,22 @@ unsafe impl Sync for XWindow {}
 unsafe impl Send for Window2 {}
 unsafe impl Sync for Window2 {}
 
+// The size constraints requested by the user, applied through the `WM_NORMAL_HINTS` property.
+struct SizeConstraints {
+    min: Option<LogicalSize>,
+    max: Option<LogicalSize>,
+    resizable: bool,
+}
+
 pub struct Window2 {
     pub x: Arc<XWindow>,
     cursor_state: Mutex<CursorState>,
+    size_constraints: Mutex<SizeConstraints>,
+    // The configuration of the CRTC before switching its video mode for exclusive fullscreen.
+    saved_crtc_config: Mutex<Option<CrtcConfig>>,
+    /// The DPI factor of the monitor the window is on, updated by the `EventsLoop` when the
+    /// window is moved.
+    pub hidpi_factor: Arc<Mutex<f64>>,
 }
 
 impl Window2 {
@@ -53,17 +175,31 @@ impl Window2 {
                -> Result<Window2, CreationError>
     {
         let display = &ctx.display;
+
+        let screen_id = match pl_attribs.screen_id {
+            Some(id) => id,
+            None => unsafe { (display.xlib.XDefaultScreen)(display.display) },
+        };
+
+        // The window doesn't have a position yet, assume it will be shown on the primary monitor.
+        let hidpi_factor = find_primary_monitor(&ctx.monitors.lock().unwrap())
+            .unwrap_or_else(|| get_primary_monitor(display))
+            .get_hidpi_factor();
+        let to_physical = |size: LogicalSize| -> (u32, u32) { size.to_physical(hidpi_factor).into() };
+        let min_dimensions = window_attrs.min_dimensions.map(&to_physical);
+        let max_dimensions = window_attrs.max_dimensions.map(&to_physical);
+
         let dimensions = {
 
             // x11 only applies constraints when the window is actively resized
             // by the user, so we have to manually apply the initial constraints
-            let mut dimensions = window_attrs.dimensions.unwrap_or((800, 600));
-            if let Some(max) = window_attrs.max_dimensions {
+            let mut dimensions = window_attrs.dimensions.map(&to_physical).unwrap_or((800, 600));
+            if let Some(max) = max_dimensions {
                 dimensions.0 = cmp::min(dimensions.0, max.0);
                 dimensions.1 = cmp::min(dimensions.1, max.1);
             }
 
-            if let Some(min) = window_attrs.min_dimensions {
+            if let Some(min) = min_dimensions {
                 dimensions.0 = cmp::max(dimensions.0, min.0);
                 dimensions.1 = cmp::max(dimensions.1, min.1);
             }
@@ -71,11 +207,6 @@ impl Window2 {
 
         };
 
-        let screen_id = match pl_attribs.screen_id {
-            Some(id) => id,
-            None => unsafe { (display.xlib.XDefaultScreen)(display.display) },
-        };
-
         // getting the root window
         let root = ctx.root;
 
@@ -91,7 +222,7 @@ impl Window2 {
             swa.event_mask = ffi::ExposureMask | ffi::StructureNotifyMask |
                 ffi::VisibilityChangeMask | ffi::KeyPressMask | ffi::PointerMotionMask |
                 ffi::KeyReleaseMask | ffi::ButtonPressMask |
-                ffi::ButtonReleaseMask | ffi::KeymapStateMask;
+                ffi::ButtonReleaseMask | ffi::KeymapStateMask | ffi::PropertyChangeMask;
             swa.border_pixel = 0;
             if window_attrs.transparent {
                 swa.background_pixel = 0;
@@ -138,6 +269,14 @@ impl Window2 {
             });
         }
 
+        // Set the initial EWMH state, must be done *before* mapping the window since window managers
+        // only read the `_NET_WM_STATE` property when they start managing it
+        let states = initial_netwm_states(window_attrs, pl_attribs);
+        if !states.is_empty() {
+            let atoms: Vec<ffi::Atom> = states.iter().map(|state| intern_atom(display, state)).collect();
+            set_netwm_state_property(display, window, &atoms);
+        }
+
         // set visibility
         if window_attrs.visible {
             unsafe {
@@ -170,12 +309,18 @@ impl Window2 {
         size_hints.flags = ffi::PSize;
         size_hints.width = dimensions.0 as i32;
         size_hints.height = dimensions.1 as i32;
-        if let Some(dimensions) = window_attrs.min_dimensions {
+        // A window can't be resized if its minimum and maximum sizes are the same
+        let (min_dimensions, max_dimensions) = if window_attrs.resizable {
+            (min_dimensions, max_dimensions)
+        } else {
+            (Some(dimensions), Some(dimensions))
+        };
+        if let Some(dimensions) = min_dimensions {
             size_hints.flags |= ffi::PMinSize;
             size_hints.min_width = dimensions.0 as i32;
             size_hints.min_height = dimensions.1 as i32;
         }
-        if let Some(dimensions) = window_attrs.max_dimensions {
+        if let Some(dimensions) = max_dimensions {
             size_hints.flags |= ffi::PMaxSize;
             size_hints.max_width = dimensions.0 as i32;
             size_hints.max_height = dimensions.1 as i32;
@@ -212,11 +357,17 @@ impl Window2 {
                 screen_id,
             }),
             cursor_state: Mutex::new(CursorState::Normal),
+            size_constraints: Mutex::new(SizeConstraints {
+                min: window_attrs.min_dimensions,
+                max: window_attrs.max_dimensions,
+                resizable: window_attrs.resizable,
+            }),
+            saved_crtc_config: Mutex::new(None),
+            hidpi_factor: Arc::new(Mutex::new(hidpi_factor)),
         };
 
         window.set_title(&window_attrs.title);
         window.set_decorations(window_attrs.decorations);
-        window.set_maximized(window_attrs.maximized);
         window.set_fullscreen(window_attrs.fullscreen.clone());
 
         if window_attrs.visible {
@@ -252,15 +403,30 @@ impl Window2 {
     }
 
     fn set_netwm(display: &Arc<XConnection>, window: ffi::Window, root: ffi::Window, property: &str, val: bool) {
-        let state_atom = unsafe {
-            with_c_str("_NET_WM_STATE", |state|
-                (display.xlib.XInternAtom)(display.display, state, 0)
-            )
-        };
-        display.check_errors().expect("Failed to call XInternAtom");
-        let atom = unsafe {
-            with_c_str(property, |state|
-                (display.xlib.XInternAtom)(display.display, state, 0)
+        let atom = intern_atom(display, property);
+
+        if is_withdrawn(display, window) {
+            let atoms: Vec<ffi::Atom> = unsafe { display.get_property32(window, b"_NET_WM_STATE\0", ffi::XA_ATOM) }
+                .unwrap_or(Vec::new())
+                .into_iter()
+                .map(|atom| atom as ffi::Atom)
+                .collect();
+            set_netwm_state_property(display, window, &toggle_netwm_state(&atoms, atom, val));
+            return;
+        }
+
+        // The first `long` is the action; `1` means add/set following property.
+        // The second `long` is the property to set, for example fullscreen.
+        Window2::send_root_message(display, window, root, "_NET_WM_STATE", &[val as c_long, atom as c_long]);
+    }
+
+    // Sends a client message about `window` to the window manager, through the root window.
+    fn send_root_message(display: &Arc<XConnection>, window: ffi::Window, root: ffi::Window,
+                         message_type: &str, values: &[c_long])
+    {
+        let message_type_atom = unsafe {
+            with_c_str(message_type, |message_type|
+                (display.xlib.XInternAtom)(display.display, message_type, 0)
             )
         };
         display.check_errors().expect("Failed to call XInternAtom");
@@ -271,14 +437,13 @@ impl Window2 {
             send_event: 1,            // true because we are sending this through `XSendEvent`
             display: display.display,
             window: window,
-            message_type: state_atom, // the _NET_WM_STATE atom is sent to change the state of a window
+            message_type: message_type_atom,
             format: 32,               // view `data` as `c_long`s
             data: {
                 let mut data = ffi::ClientMessageData::new();
-                // This first `long` is the action; `1` means add/set following property.
-                data.set_long(0, val as c_long);
-                // This second `long` is the property to set (fullscreen)
-                data.set_long(1, atom as c_long);
+                for (i, &value) in values.iter().enumerate() {
+                    data.set_long(i, value);
+                }
                 data
             }
         };
@@ -296,62 +461,101 @@ impl Window2 {
         }
     }
 
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorId>) {
-        match monitor {
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.restore_video_mode();
+        match fullscreen {
             None => {
                 self.set_fullscreen_hint(false);
             },
-            Some(RootMonitorId { inner: PlatformMonitorId::X(monitor) }) => {
-                let screenpos = monitor.get_position();
-                self.set_position(screenpos.0 as i32, screenpos.1 as i32);
+            Some(fullscreen) => {
+                let monitor = match *fullscreen.get_monitor() {
+                    RootMonitorId { inner: PlatformMonitorId::X(ref monitor) } => monitor.clone(),
+                    _ => {
+                        eprintln!("[winit] Something's broken, got an unknown fullscreen state in X11");
+                        return;
+                    }
+                };
+                if let Fullscreen::Exclusive(ref video_mode) = fullscreen {
+                    // If the mode can't be set, the window is still made fullscreen with the
+                    // current mode of the monitor.
+                    let mode = video_mode.native_mode as ffi::RRMode;
+                    if let Ok(config) = monitor.set_video_mode(&self.x.display, mode) {
+                        *self.saved_crtc_config.lock().unwrap() = Some(config);
+                    }
+                }
+                let (x, y) = monitor.get_position();
+                self.set_physical_position(x, y);
                 self.set_fullscreen_hint(true);
             }
-            _ => {
-                eprintln!("[winit] Something's broken, got an unknown fullscreen state in X11");
-            }
         }
     }
 
+    fn restore_video_mode(&self) {
+        if let Some(config) = self.saved_crtc_config.lock().unwrap().take() {
+            restore_crtc_config(&self.x.display, &config);
+        }
+    }
+
+    #[inline]
     pub fn get_current_monitor(&self) -> X11MonitorId {
         let monitors = get_available_monitors(&self.x.display);
-        let default = monitors[0].clone();
+        get_monitor_for_window(&self.x.display, self.x.window, &monitors)
+            .unwrap_or_else(|| get_primary_monitor(&self.x.display))
+    }
 
-        let (wx,wy) = match self.get_position() {
-            Some(val) => (cmp::max(0,val.0) as u32, cmp::max(0,val.1) as u32),
-            None=> return default,
-        };
-        let (ww,wh) = match self.get_outer_size() {
-            Some(val) => val,
-            None=> return default,
-        };
-        // Opposite corner coordinates
-        let (wxo, wyo) = (wx+ww-1, wy+wh-1);
-
-        // Find the monitor with the biggest overlap with the window
-        let mut overlap = 0;
-        let mut find = default;
-        for monitor in monitors {
-            let (mx, my) = monitor.get_position();
-            let mx = mx as u32;
-            let my = my as u32;
-            let (mw, mh) = monitor.get_dimensions();
-            let (mxo, myo) = (mx+mw-1, my+mh-1);
-            let (ox, oy) = (cmp::max(wx, mx), cmp::max(wy, my));
-            let (oxo, oyo) = (cmp::min(wxo, mxo), cmp::min(wyo, myo));
-            let osize = if ox <= oxo || oy <= oyo { 0 } else { (oxo-ox)*(oyo-oy) };
-
-            if osize > overlap {
-                overlap = osize;
-                find = monitor;
+    pub fn set_maximized(&self, maximized: bool) {
+        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_MAXIMIZED_HORZ", maximized);
+        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_MAXIMIZED_VERT", maximized);
+    }
+
+    pub fn set_minimized(&self, minimized: bool) {
+        if minimized {
+            unsafe {
+                // Sends the `WM_CHANGE_STATE` client message to the window manager for us.
+                (self.x.display.xlib.XIconifyWindow)(self.x.display.display, self.x.window, self.x.screen_id);
+                (self.x.display.xlib.XFlush)(self.x.display.display);
+                self.x.display.check_errors().expect("Failed to call XIconifyWindow");
             }
+        } else {
+            // Mapping an iconified window restores it.
+            self.show();
         }
+    }
 
-        find
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_ABOVE", always_on_top);
     }
 
-    pub fn set_maximized(&self, maximized: bool) {
-        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_MAXIMIZED_HORZ", maximized);
-        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_MAXIMIZED_VERT", maximized);
+    pub fn set_sticky(&self, sticky: bool) {
+        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_STICKY", sticky);
+    }
+
+    pub fn set_skip_taskbar(&self, skip_taskbar: bool) {
+        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_SKIP_TASKBAR", skip_taskbar);
+    }
+
+    pub fn set_skip_pager(&self, skip_pager: bool) {
+        Window2::set_netwm(&self.x.display, self.x.window, self.x.root, "_NET_WM_STATE_SKIP_PAGER", skip_pager);
+    }
+
+    pub fn set_desktop(&self, desktop: u32) {
+        if is_withdrawn(&self.x.display, self.x.window) {
+            // The window manager reads the property when it starts managing the window.
+            let net_wm_desktop = intern_atom(&self.x.display, "_NET_WM_DESKTOP");
+            let desktop = desktop as c_long;
+            unsafe {
+                (self.x.display.xlib.XChangeProperty)(self.x.display.display, self.x.window, net_wm_desktop,
+                                                      ffi::XA_CARDINAL, 32, ffi::PropModeReplace,
+                                                      &desktop as *const c_long as *const u8, 1);
+                (self.x.display.xlib.XFlush)(self.x.display.display);
+            }
+            self.x.display.check_errors().expect("Failed to set the _NET_WM_DESKTOP property");
+            return;
+        }
+
+        // The second `long` is the source of the request; `1` means a normal application.
+        Window2::send_root_message(&self.x.display, self.x.window, self.x.root, "_NET_WM_DESKTOP",
+                                   &[desktop as c_long, 1]);
     }
 
     fn set_fullscreen_hint(&self, fullscreen: bool) {
@@ -440,6 +644,11 @@ impl Window2 {
         }
     }
 
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        get_window_state(&self.x.display, self.x.window)
+    }
+
     fn get_geometry(&self) -> Option<(i32, i32, u32, u32, u32)> {
         unsafe {
             use std::mem;
@@ -464,31 +673,120 @@ impl Window2 {
     }
 
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
-        self.get_geometry().map(|(x, y, _, _, _)| (x, y))
+    pub fn get_position(&self) -> Option<LogicalPosition> {
+        self.get_geometry().map(|(x, y, _, _, _)| LogicalPosition::from_physical((x, y), self.hidpi_factor()))
     }
 
-    pub fn set_position(&self, x: i32, y: i32) {
+    #[inline]
+    pub fn set_position(&self, position: LogicalPosition) {
+        let (x, y) = position.to_physical(self.hidpi_factor()).into();
+        self.set_physical_position(x, y);
+    }
+
+    fn set_physical_position(&self, x: i32, y: i32) {
         unsafe { (self.x.display.xlib.XMoveWindow)(self.x.display.display, self.x.window, x as libc::c_int, y as libc::c_int); }
         self.x.display.check_errors().expect("Failed to call XMoveWindow");
     }
 
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
-        self.get_geometry().map(|(_, _, w, h, _)| (w, h))
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
+        self.get_geometry().map(|(_, _, w, h, _)| LogicalSize::from_physical((w, h), self.hidpi_factor()))
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
-        self.get_geometry().map(|(_, _, w, h, b)| (w + b, h + b))       // TODO: is this really outside?
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
+        // TODO: is this really outside?
+        self.get_geometry().map(|(_, _, w, h, b)| LogicalSize::from_physical((w + b, h + b), self.hidpi_factor()))
     }
 
     #[inline]
-    pub fn set_inner_size(&self, x: u32, y: u32) {
-        unsafe { (self.x.display.xlib.XResizeWindow)(self.x.display.display, self.x.window, x as libc::c_uint, y as libc::c_uint); }
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        let (width, height): (u32, u32) = size.to_physical(self.hidpi_factor()).into();
+        if !self.size_constraints.lock().unwrap().resizable {
+            // The size hints pin non-resizable windows to their size, they must follow the new one.
+            self.pin_size_hints(Some((width, height)));
+        }
+        unsafe { (self.x.display.xlib.XResizeWindow)(self.x.display.display, self.x.window, width as libc::c_uint, height as libc::c_uint); }
         self.x.display.check_errors().expect("Failed to call XResizeWindow");
     }
 
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.size_constraints.lock().unwrap().min = dimensions;
+        self.update_size_hints();
+    }
+
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.size_constraints.lock().unwrap().max = dimensions;
+        self.update_size_hints();
+    }
+
+    pub fn set_resizable(&self, resizable: bool) {
+        self.size_constraints.lock().unwrap().resizable = resizable;
+        self.update_size_hints();
+    }
+
+    #[inline]
+    fn update_size_hints(&self) {
+        self.pin_size_hints(None);
+    }
+
+    // Non-resizable windows are pinned to `size`, or to their current size if it's `None`.
+    fn pin_size_hints(&self, size: Option<(u32, u32)>) {
+        let constraints = self.size_constraints.lock().unwrap();
+        let hidpi_factor = self.hidpi_factor();
+        let to_physical = |size: LogicalSize| -> (u32, u32) { size.to_physical(hidpi_factor).into() };
+        let (min, max) = if constraints.resizable {
+            (constraints.min.map(&to_physical), constraints.max.map(&to_physical))
+        } else {
+            let size = size.or_else(|| self.get_geometry().map(|(_, _, w, h, _)| (w, h)));
+            (size, size)
+        };
+
+        // Only the min and max sizes are replaced, the other hints (such as `PSize`) are kept.
+        let mut size_hints: ffi::XSizeHints = unsafe { mem::zeroed() };
+        unsafe {
+            let mut supplied = 0;
+            (self.x.display.xlib.XGetWMNormalHints)(self.x.display.display, self.x.window,
+                                                    &mut size_hints, &mut supplied);
+        }
+        self.x.display.check_errors().expect("Failed to call XGetWMNormalHints");
+        size_hints.flags &= !(ffi::PMinSize | ffi::PMaxSize);
+        if let Some(dimensions) = min {
+            size_hints.flags |= ffi::PMinSize;
+            size_hints.min_width = dimensions.0 as i32;
+            size_hints.min_height = dimensions.1 as i32;
+        }
+        if let Some(dimensions) = max {
+            size_hints.flags |= ffi::PMaxSize;
+            size_hints.max_width = dimensions.0 as i32;
+            size_hints.max_height = dimensions.1 as i32;
+        }
+        unsafe {
+            (self.x.display.xlib.XSetWMNormalHints)(self.x.display.display, self.x.window, &mut size_hints);
+        }
+        self.x.display.check_errors().expect("Failed to call XSetWMNormalHints");
+
+        // x11 only applies constraints when the window is actively resized by the user
+        if let Some((_, _, width, height, _)) = self.get_geometry() {
+            let mut dimensions = (width, height);
+            if let Some(max) = max {
+                dimensions.0 = cmp::min(dimensions.0, max.0);
+                dimensions.1 = cmp::min(dimensions.1, max.1);
+            }
+            if let Some(min) = min {
+                dimensions.0 = cmp::max(dimensions.0, min.0);
+                dimensions.1 = cmp::max(dimensions.1, min.1);
+            }
+            if dimensions != (width, height) {
+                unsafe {
+                    (self.x.display.xlib.XResizeWindow)(self.x.display.display, self.x.window,
+                                                        dimensions.0 as libc::c_uint, dimensions.1 as libc::c_uint);
+                }
+                self.x.display.check_errors().expect("Failed to call XResizeWindow");
+            }
+        }
+    }
+
     #[inline]
     pub fn get_xlib_display(&self) -> *mut libc::c_void {
         self.x.display.display as *mut libc::c_void
@@ -702,24 +1000,81 @@ impl Window2 {
         }
     }
 
-    pub fn hidpi_factor(&self) -> f32 {
-        unsafe {
-            let x_px = (self.x.display.xlib.XDisplayWidth)(self.x.display.display, self.x.screen_id);
-            let y_px = (self.x.display.xlib.XDisplayHeight)(self.x.display.display, self.x.screen_id);
-            let x_mm = (self.x.display.xlib.XDisplayWidthMM)(self.x.display.display, self.x.screen_id);
-            let y_mm = (self.x.display.xlib.XDisplayHeightMM)(self.x.display.display, self.x.screen_id);
-            let ppmm = ((x_px as f32 * y_px as f32) / (x_mm as f32 * y_mm as f32)).sqrt();
-            ((ppmm * (12.0 * 25.4 / 96.0)).round() / 12.0).max(1.0) // quantize with 1/12 step size.
-        }
+    #[inline]
+    pub fn hidpi_factor(&self) -> f64 {
+        *self.hidpi_factor.lock().unwrap()
     }
 
-    pub fn set_cursor_position(&self, x: i32, y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), ()> {
+        let (x, y) = position.to_physical(self.hidpi_factor()).into();
         unsafe {
             (self.x.display.xlib.XWarpPointer)(self.x.display.display, 0, self.x.window, 0, 0, 0, 0, x, y);
             self.x.display.check_errors().map_err(|_| ())
         }
     }
 
+    /// Simulates a motion of the pointer to `position`, relative to the top-left corner of the
+    /// window, through XTest.
+    pub fn fake_motion(&self, position: LogicalPosition) -> Result<(), ()> {
+        let (x, y) = position.to_physical(self.hidpi_factor()).into();
+        let display = &self.x.display;
+        let (root_x, root_y) = unsafe {
+            let root = (display.xlib.XRootWindow)(display.display, self.x.screen_id);
+            let (mut root_x, mut root_y, mut child) = (0, 0, 0);
+            if (display.xlib.XTranslateCoordinates)(display.display, self.x.window, root, x, y,
+                                                     &mut root_x, &mut root_y, &mut child) == 0
+            {
+                return Err(());
+            }
+            (root_x, root_y)
+        };
+        display.fake_motion(self.x.screen_id, root_x, root_y)
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId { WindowId(self.x.window) }
 }
+
+impl Drop for Window2 {
+    fn drop(&mut self) {
+        self.restore_video_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use WindowAttributes;
+    use platform::PlatformSpecificWindowBuilderAttributes;
+    use super::{initial_netwm_states, toggle_netwm_state};
+
+    #[test]
+    fn initial_states_follow_the_builder() {
+        let window_attrs = WindowAttributes::default();
+        let pl_attribs = PlatformSpecificWindowBuilderAttributes::default();
+        assert!(initial_netwm_states(&window_attrs, &pl_attribs).is_empty());
+
+        let window_attrs = WindowAttributes { maximized: true, always_on_top: true, .. window_attrs };
+        let pl_attribs = PlatformSpecificWindowBuilderAttributes {
+            sticky: true,
+            skip_taskbar: true,
+            skip_pager: true,
+            .. pl_attribs
+        };
+        assert_eq!(initial_netwm_states(&window_attrs, &pl_attribs), vec![
+            "_NET_WM_STATE_MAXIMIZED_HORZ",
+            "_NET_WM_STATE_MAXIMIZED_VERT",
+            "_NET_WM_STATE_ABOVE",
+            "_NET_WM_STATE_STICKY",
+            "_NET_WM_STATE_SKIP_TASKBAR",
+            "_NET_WM_STATE_SKIP_PAGER",
+        ]);
+    }
+
+    #[test]
+    fn toggling_states_keeps_the_others() {
+        assert_eq!(toggle_netwm_state(&[], 1, true), vec![1]);
+        assert_eq!(toggle_netwm_state(&[1, 2], 2, true), vec![1, 2]);
+        assert_eq!(toggle_netwm_state(&[1, 2, 3], 2, false), vec![1, 3]);
+        assert_eq!(toggle_netwm_state(&[1, 3], 2, false), vec![1, 3]);
+    }
+}
@@ -1,10 +1,16 @@
 use std::ptr;
+use std::slice;
 use std::fmt;
 use std::error::Error;
+use std::collections::HashMap;
 use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::os::raw::{c_int, c_long, c_uchar};
 
 use libc;
 
+use window::GammaRamp;
+
 use super::ffi;
 
 /// A connection to an X server.
@@ -14,26 +20,39 @@ pub struct XConnection {
     pub xcursor: ffi::Xcursor,
     pub xinput2: ffi::XInput2,
     pub xlib_xcb: ffi::Xlib_xcb,
+    /// `None` if libXtst or the XTEST extension of the server is missing.
+    pub xtest: Option<ffi::XTest>,
     pub display: *mut ffi::Display,
     pub latest_error: Mutex<Option<XError>>,
+    /// The gamma ramps of the CRTCs before we first changed them. They are shared by all the
+    /// `EventsLoop`s of the connection, and restored when the last of them is dropped.
+    pub saved_gamma_ramps: Mutex<HashMap<ffi::RRCrtc, GammaRamp>>,
+    /// The number of `EventsLoop`s alive on this connection.
+    pub events_loops: AtomicUsize,
 }
 
 unsafe impl Send for XConnection {}
 unsafe impl Sync for XConnection {}
 
 pub type XErrorHandler = Option<unsafe extern fn(*mut ffi::Display, *mut ffi::XErrorEvent) -> libc::c_int>;
+pub type XIOErrorHandler = Option<unsafe extern fn(*mut ffi::Display) -> libc::c_int>;
 
 impl XConnection {
-    pub fn new(error_handler: XErrorHandler) -> Result<XConnection, XNotSupported> {
+    pub fn new(error_handler: XErrorHandler, io_error_handler: XIOErrorHandler)
+               -> Result<XConnection, XNotSupported>
+    {
         // opening the libraries
         let xlib = try!(ffi::Xlib::open());
         let xcursor = try!(ffi::Xcursor::open());
         let xrandr = try!(ffi::Xrandr::open());
         let xinput2 = try!(ffi::XInput2::open());
         let xlib_xcb = try!(ffi::Xlib_xcb::open());
+        // XTest is only used to simulate input, so we can do without it.
+        let xtest = ffi::XTest::open().ok();
 
         unsafe { (xlib.XInitThreads)() };
         unsafe { (xlib.XSetErrorHandler)(error_handler) };
+        unsafe { (xlib.XSetIOErrorHandler)(io_error_handler) };
 
         // calling XOpenDisplay
         let display = unsafe {
@@ -44,17 +63,55 @@ impl XConnection {
             display
         };
 
+        let xtest = xtest.and_then(|xtest| unsafe {
+            let (mut event_base, mut error_base, mut major, mut minor) = (0, 0, 0, 0);
+            let present = (xtest.XTestQueryExtension)(display, &mut event_base, &mut error_base,
+                                                      &mut major, &mut minor);
+            if present == ffi::False { None } else { Some(xtest) }
+        });
+
         Ok(XConnection {
             xlib: xlib,
             xrandr: xrandr,
             xcursor: xcursor,
             xinput2: xinput2,
             xlib_xcb: xlib_xcb,
+            xtest: xtest,
             display: display,
             latest_error: Mutex::new(None),
+            saved_gamma_ramps: Mutex::new(HashMap::new()),
+            events_loops: AtomicUsize::new(0),
         })
     }
 
+    /// Reads a property of `window` made of 32-bit items of type `property_type`, for example
+    /// `XA_CARDINAL` or `XA_ATOM`. Returns `None` if the property doesn't exist or has another type.
+    pub unsafe fn get_property32(&self, window: ffi::Window, name: &[u8], property_type: ffi::Atom)
+                                 -> Option<Vec<c_long>>
+    {
+        let atom = (self.xlib.XInternAtom)(self.display, name.as_ptr() as *const _, 1);
+        if atom == 0 {
+            return None;
+        }
+        let (mut actual_type, mut actual_format, mut nitems, mut bytes_after) = (0, 0, 0, 0);
+        let mut data: *mut c_uchar = ptr::null_mut();
+        let status = (self.xlib.XGetWindowProperty)(self.display, window, atom, 0, c_long::max_value(),
+                                                    0, property_type, &mut actual_type,
+                                                    &mut actual_format, &mut nitems, &mut bytes_after,
+                                                    &mut data);
+        if status != ffi::Success as c_int || data.is_null() {
+            return None;
+        }
+        // Items of 32-bit properties are stored as `long`s by Xlib.
+        let values = if actual_type == property_type && actual_format == 32 {
+            Some(slice::from_raw_parts(data as *const c_long, nitems as usize).to_vec())
+        } else {
+            None
+        };
+        (self.xlib.XFree)(data as *mut _);
+        values
+    }
+
     /// Checks whether an error has been triggered by the previous function calls.
     #[inline]
     pub fn check_errors(&self) -> Result<(), XError> {
@@ -72,6 +129,41 @@ impl XConnection {
     pub fn ignore_error(&self) {
         *self.latest_error.lock().unwrap() = None;
     }
+
+    /// Simulates a press or release of the key with the X keycode `keycode` through XTest.
+    ///
+    /// Returns an error if XTest isn't available.
+    pub fn fake_key(&self, keycode: libc::c_uint, pressed: bool) -> Result<(), ()> {
+        let xtest = try!(self.xtest.as_ref().ok_or(()));
+        unsafe { (xtest.XTestFakeKeyEvent)(self.display, keycode, pressed as libc::c_int, ffi::CurrentTime) };
+        self.flush_fake_event()
+    }
+
+    /// Simulates a press or release of the pointer button `button` through XTest.
+    ///
+    /// Returns an error if XTest isn't available.
+    pub fn fake_button(&self, button: libc::c_uint, pressed: bool) -> Result<(), ()> {
+        let xtest = try!(self.xtest.as_ref().ok_or(()));
+        unsafe { (xtest.XTestFakeButtonEvent)(self.display, button, pressed as libc::c_int, ffi::CurrentTime) };
+        self.flush_fake_event()
+    }
+
+    /// Simulates a motion of the pointer to `(x, y)` on the screen `screen` through XTest. A
+    /// `screen` of -1 means the screen the pointer is on.
+    ///
+    /// Returns an error if XTest isn't available.
+    pub fn fake_motion(&self, screen: libc::c_int, x: libc::c_int, y: libc::c_int) -> Result<(), ()> {
+        let xtest = try!(self.xtest.as_ref().ok_or(()));
+        unsafe { (xtest.XTestFakeMotionEvent)(self.display, screen, x, y, ffi::CurrentTime) };
+        self.flush_fake_event()
+    }
+
+    // Sends a fake event to the server right away, so that the events it generates arrive
+    // without the caller having to flush.
+    fn flush_fake_event(&self) -> Result<(), ()> {
+        unsafe { (self.xlib.XFlush)(self.display) };
+        self.check_errors().map_err(|_| ())
+    }
 }
 
 impl Drop for XConnection {
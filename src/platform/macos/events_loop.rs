@@ -2,6 +2,7 @@ use {ControlFlow, EventsLoopClosed};
 use cocoa::{self, appkit, foundation};
 use cocoa::appkit::{NSApplication, NSEvent, NSView, NSWindow};
 use events::{self, ElementState, Event, MouseButton, TouchPhase, WindowEvent, DeviceEvent, ModifiersState, KeyboardInput};
+use dpi::LogicalPosition;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex, Weak};
 use super::window::Window2;
@@ -169,6 +170,10 @@ impl EventsLoop {
         }
     }
 
+    pub fn try_new() -> Result<Self, ::EventsLoopCreationError> {
+        Ok(EventsLoop::new())
+    }
+
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(Event),
     {
@@ -210,6 +215,13 @@ impl EventsLoop {
         self.shared.user_callback.drop();
     }
 
+    // TODO: actually wait for the next event
+    pub fn poll_events_timeout<F>(&mut self, _timeout: std::time::Duration, callback: F)
+        where F: FnMut(Event)
+    {
+        self.poll_events(callback)
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(Event) -> ControlFlow
     {
@@ -234,6 +246,9 @@ impl EventsLoop {
             unsafe {
                 // First, yield all pending events.
                 self.shared.call_user_callback_with_pending_events();
+
+                // Report the end of the batch before waiting.
+                self.shared.user_callback.call_with_event(Event::EventsCleared);
                 if let ControlFlow::Break = control_flow.get() {
                     break;
                 }
@@ -472,21 +487,21 @@ impl EventsLoop {
                 let mut events = std::collections::VecDeque::new();
 
                 {
-                    let x = (scale_factor * view_point.x as f32) as f64;
-                    let y = (scale_factor * (view_rect.size.height - view_point.y) as f32) as f64;
-                    let window_event = WindowEvent::MouseMoved { device_id: DEVICE_ID, position: (x, y) };
+                    let x = view_point.x as f64;
+                    let y = (view_rect.size.height - view_point.y) as f64;
+                    let window_event = WindowEvent::MouseMoved { device_id: DEVICE_ID, position: LogicalPosition::new(x, y) };
                     let event = Event::WindowEvent { window_id: ::WindowId(window.id()), event: window_event };
                     events.push_back(event);
                 }
 
-                let delta_x = (scale_factor * ns_event.deltaX() as f32) as f64;
+                let delta_x = scale_factor * ns_event.deltaX() as f64;
                 if delta_x != 0.0 {
                     let motion_event = DeviceEvent::Motion { axis: 0, value: delta_x };
                     let event = Event::DeviceEvent{ device_id: DEVICE_ID, event: motion_event };
                     events.push_back(event);
                 }
 
-                let delta_y = (scale_factor * ns_event.deltaY() as f32) as f64;
+                let delta_y = scale_factor * ns_event.deltaY() as f64;
                 if delta_y != 0.0 {
                     let motion_event = DeviceEvent::Motion { axis: 1, value: delta_y };
                     let event = Event::DeviceEvent{ device_id: DEVICE_ID, event: motion_event };
@@ -506,7 +521,7 @@ impl EventsLoop {
                 };
 
                 use events::MouseScrollDelta::{LineDelta, PixelDelta};
-                let scale_factor = window.hidpi_factor();
+                let scale_factor = window.hidpi_factor() as f32;
                 let delta = if ns_event.hasPreciseScrollingDeltas() == cocoa::base::YES {
                     PixelDelta(scale_factor * ns_event.scrollingDeltaX() as f32,
                                scale_factor * ns_event.scrollingDeltaY() as f32)
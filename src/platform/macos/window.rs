@@ -3,6 +3,7 @@ use CreationError::OsError;
 use libc;
 
 use WindowAttributes;
+use dpi::{LogicalPosition, LogicalSize};
 use os::macos::ActivationPolicy;
 use os::macos::WindowExt;
 
@@ -12,7 +13,7 @@ use objc::declare::ClassDecl;
 
 use cocoa;
 use cocoa::base::{id, nil};
-use cocoa::foundation::{NSPoint, NSRect, NSSize, NSString, NSUInteger};
+use cocoa::foundation::{NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger};
 use cocoa::appkit::{self, NSApplication, NSColor, NSView, NSWindow};
 
 use core_graphics::display::{CGAssociateMouseAndMouseCursorPosition, CGMainDisplayID, CGDisplayPixelsHigh, CGWarpMouseCursorPosition};
@@ -61,10 +62,8 @@ impl WindowDelegate {
         // Called when the window is resized or when the window was moved to a different screen.
         unsafe fn emit_resize_event(state: &mut DelegateState) {
             let rect = NSView::frame(*state.view);
-            let scale_factor = NSWindow::backingScaleFactor(*state.window) as f32;
-            let width = (scale_factor * rect.size.width as f32) as u32;
-            let height = (scale_factor * rect.size.height as f32) as u32;
-            emit_event(state, WindowEvent::Resized(width, height));
+            let size = LogicalSize::new(rect.size.width as f64, rect.size.height as f64);
+            emit_event(state, WindowEvent::Resized(size));
         }
 
         extern fn window_should_close(this: &Object, _: Sel, _: id) -> BOOL {
@@ -335,12 +334,12 @@ impl Window2 {
                 window.makeKeyWindow();
             }
 
-            if let Some((width, height)) = win_attribs.min_dimensions {
-                nswindow_set_min_dimensions(window.0, width.into(), height.into());
+            if let Some(size) = win_attribs.min_dimensions {
+                nswindow_set_min_dimensions(window.0, size.width, size.height);
             }
 
-            if let Some((width, height)) = win_attribs.max_dimensions {
-                nswindow_set_max_dimensions(window.0, width.into(), height.into());
+            if let Some(size) = win_attribs.max_dimensions {
+                nswindow_set_max_dimensions(window.0, size.width, size.height);
             }
 
             use cocoa::foundation::NSArray;
@@ -361,6 +360,10 @@ impl Window2 {
             delegate: WindowDelegate::new(ds),
         };
 
+        if win_attribs.always_on_top {
+            window.set_always_on_top(true);
+        }
+
         Ok(window)
     }
 
@@ -384,8 +387,8 @@ impl Window2 {
     fn create_window(attrs: &WindowAttributes) -> Option<IdRef> {
         unsafe {
             let screen = match attrs.fullscreen {
-                Some(ref monitor_id) => {
-                    let native_id = monitor_id.inner.get_native_identifier();
+                Some(ref fullscreen) => {
+                    let native_id = fullscreen.get_monitor().inner.get_native_identifier();
                     let matching_screen = {
                         let screens = appkit::NSScreen::screens(nil);
                         let count: NSUInteger = msg_send![screens, count];
@@ -412,8 +415,8 @@ impl Window2 {
             let frame = match screen {
                 Some(screen) => appkit::NSScreen::frame(screen),
                 None => {
-                    let (width, height) = attrs.dimensions.unwrap_or((800, 600));
-                    NSRect::new(NSPoint::new(0., 0.), NSSize::new(width as f64, height as f64))
+                    let size = attrs.dimensions.unwrap_or(LogicalSize::new(800.0, 600.0));
+                    NSRect::new(NSPoint::new(0., 0.), NSSize::new(size.width, size.height))
                 }
             };
 
@@ -432,6 +435,12 @@ impl Window2 {
                     appkit::NSFullSizeContentViewWindowMask
             };
 
+            let masks = if attrs.resizable {
+                masks
+            } else {
+                masks & !appkit::NSResizableWindowMask
+            };
+
             let window = IdRef::new(NSWindow::alloc(nil).initWithContentRect_styleMask_backing_defer_(
                 frame,
                 masks,
@@ -488,17 +497,17 @@ impl Window2 {
         unsafe { NSWindow::orderOut_(*self.window, nil); }
     }
 
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         unsafe {
             let content_rect = NSWindow::contentRectForFrameRect_(*self.window, NSWindow::frame(*self.window));
 
             // TODO: consider extrapolating the calculations for the y axis to
             // a private method
-            Some((content_rect.origin.x as i32, (CGDisplayPixelsHigh(CGMainDisplayID()) as f64 - (content_rect.origin.y + content_rect.size.height)) as i32))
+            Some(LogicalPosition::new(content_rect.origin.x as f64, CGDisplayPixelsHigh(CGMainDisplayID()) as f64 - (content_rect.origin.y + content_rect.size.height) as f64))
         }
     }
 
-    pub fn set_position(&self, x: i32, y: i32) {
+    pub fn set_position(&self, position: LogicalPosition) {
         unsafe {
             let frame = NSWindow::frame(*self.view);
 
@@ -510,7 +519,7 @@ impl Window2 {
 
             // TODO: consider extrapolating the calculations for the y axis to
             // a private method
-            let dummy = NSRect::new(NSPoint::new(x as f64, CGDisplayPixelsHigh(CGMainDisplayID()) as f64 - (frame.size.height + y as f64)), NSSize::new(0f64, 0f64));
+            let dummy = NSRect::new(NSPoint::new(position.x, CGDisplayPixelsHigh(CGMainDisplayID()) as f64 - (frame.size.height + position.y)), NSSize::new(0f64, 0f64));
             let conv = NSWindow::frameRectForContentRect_(*self.window, dummy);
 
             // NSWindow::setFrameTopLeftPoint_(*self.window, conv.origin);
@@ -519,25 +528,25 @@ impl Window2 {
     }
 
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         unsafe {
             let view_frame = NSView::frame(*self.view);
-            Some((view_frame.size.width as u32, view_frame.size.height as u32))
+            Some(LogicalSize::new(view_frame.size.width as f64, view_frame.size.height as f64))
         }
     }
 
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         unsafe {
             let window_frame = NSWindow::frame(*self.window);
-            Some((window_frame.size.width as u32, window_frame.size.height as u32))
+            Some(LogicalSize::new(window_frame.size.width as f64, window_frame.size.height as f64))
         }
     }
 
     #[inline]
-    pub fn set_inner_size(&self, width: u32, height: u32) {
+    pub fn set_inner_size(&self, size: LogicalSize) {
         unsafe {
-            NSWindow::setContentSize_(*self.window, NSSize::new(width as f64, height as f64));
+            NSWindow::setContentSize_(*self.window, NSSize::new(size.width, size.height));
         }
     }
 
@@ -612,16 +621,16 @@ impl Window2 {
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
+    pub fn hidpi_factor(&self) -> f64 {
         unsafe {
-            NSWindow::backingScaleFactor(*self.window) as f32
+            NSWindow::backingScaleFactor(*self.window) as f64
         }
     }
 
     #[inline]
-    pub fn set_cursor_position(&self, x: i32, y: i32) -> Result<(), ()> {
-        let (window_x, window_y) = self.get_position().unwrap_or((0, 0));
-        let (cursor_x, cursor_y) = (window_x + x, window_y + y);
+    pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), ()> {
+        let window_position = self.get_position().unwrap_or(LogicalPosition::new(0.0, 0.0));
+        let (cursor_x, cursor_y) = (window_position.x + position.x, window_position.y + position.y);
 
         unsafe {
             // TODO: Check for errors.
@@ -635,16 +644,76 @@ impl Window2 {
         Ok(())
     }
 
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        let dimensions = dimensions.unwrap_or(LogicalSize::new(0.0, 0.0));
+        unsafe {
+            nswindow_set_min_dimensions(*self.window, dimensions.width, dimensions.height);
+        }
+    }
+
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        // The default maximum size of Cocoa windows is `FLT_MAX`
+        let dimensions = dimensions.unwrap_or(LogicalSize::new(std::f32::MAX as f64, std::f32::MAX as f64));
+        unsafe {
+            nswindow_set_max_dimensions(*self.window, dimensions.width, dimensions.height);
+        }
+    }
+
+    pub fn set_resizable(&self, resizable: bool) {
+        unsafe {
+            let mask: NSUInteger = msg_send![*self.window, styleMask];
+            let resizable_mask = appkit::NSResizableWindowMask.bits() as NSUInteger;
+            let mask = if resizable { mask | resizable_mask } else { mask & !resizable_mask };
+            let _: () = msg_send![*self.window, setStyleMask:mask];
+        }
+    }
+
     #[inline]
     pub fn set_maximized(&self, _maximized: bool) {
         unimplemented!()
     }
 
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        // NSFloatingWindowLevel and NSNormalWindowLevel
+        let level: NSInteger = if always_on_top { 3 } else { 0 };
+        unsafe {
+            let _: () = msg_send![*self.window, setLevel:level];
+        }
+    }
+
+    pub fn set_minimized(&self, minimized: bool) {
+        unsafe {
+            if minimized {
+                let _: () = msg_send![*self.window, miniaturize:nil];
+            } else {
+                let _: () = msg_send![*self.window, deminiaturize:nil];
+            }
+        }
+    }
+
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
+    pub fn set_fullscreen(&self, _fullscreen: Option<::Fullscreen>) {
         unimplemented!()
     }
 
+    pub fn get_state(&self) -> ::WindowState {
+        unsafe {
+            let mask: NSUInteger = msg_send![*self.window, styleMask];
+            let is_zoomed: BOOL = msg_send![*self.window, isZoomed];
+            let is_miniaturized: BOOL = msg_send![*self.window, isMiniaturized];
+            let is_key_window: BOOL = msg_send![*self.window, isKeyWindow];
+            let is_visible: BOOL = msg_send![*self.window, isVisible];
+            ::WindowState {
+                maximized: is_zoomed == YES,
+                minimized: is_miniaturized == YES,
+                fullscreen: mask & appkit::NSFullScreenWindowMask.bits() as NSUInteger != 0,
+                focused: is_key_window == YES,
+                // Miniaturized windows aren't visible for Cocoa.
+                visible: is_visible == YES || is_miniaturized == YES,
+            }
+        }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         unimplemented!()
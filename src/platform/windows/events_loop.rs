@@ -20,11 +20,13 @@ use std::os::windows::ffi::OsStringExt;
 use std::os::windows::io::AsRawHandle;
 use std::ptr;
 use std::sync::mpsc;
+use std::sync::mpsc::{RecvTimeoutError, TryRecvError};
 use std::sync::Arc;
 use std::sync::Barrier;
 use std::sync::Mutex;
 use std::sync::Condvar;
 use std::thread;
+use std::time::{Duration, Instant};
 
 use kernel32;
 use shell32;
@@ -36,10 +38,13 @@ use platform::platform::Cursor;
 use platform::platform::WindowId;
 use platform::platform::DEVICE_ID;
 
+use dpi::{LogicalPosition, LogicalSize};
+
 use ControlFlow;
 use CursorState;
 use Event;
 use EventsLoopClosed;
+use EventsLoopCreationError;
 use KeyboardInput;
 use WindowAttributes;
 use WindowEvent;
@@ -153,6 +158,10 @@ impl EventsLoop {
         }
     }
 
+    pub fn try_new() -> Result<EventsLoop, EventsLoopCreationError> {
+        Ok(EventsLoop::new())
+    }
+
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(Event)
     {
@@ -161,46 +170,80 @@ impl EventsLoop {
                 Ok(e) => e,
                 Err(_) => return
             };
-            let is_resize = match event {
-                Event::WindowEvent{ event: WindowEvent::Resized(..), .. } => true,
-                _ => false
-            };
-
-            callback(event);
-            if is_resize {
-                let (ref mutex, ref cvar) = *self.win32_block_loop;
-                let mut block_thread = mutex.lock().unwrap();
-                *block_thread = false;
-                cvar.notify_all();
-            }
+            self.call_callback(event, &mut callback);
         }
     }
 
+    pub fn poll_events_timeout<F>(&mut self, timeout: Duration, mut callback: F)
+        where F: FnMut(Event)
+    {
+        // Wait for the first event, then handle the other ones that are pending.
+        let event = match self.receiver.recv_timeout(timeout) {
+            Ok(e) => e,
+            Err(_) => return
+        };
+        self.call_callback(event, &mut callback);
+        self.poll_events(callback);
+    }
+
     pub fn run_forever<F>(&mut self, mut callback: F)
         where F: FnMut(Event) -> ControlFlow
     {
+        let mut flow = ControlFlow::Continue;
         loop {
-            let event = match self.receiver.recv() {
+            if flow == ControlFlow::Break {
+                break;
+            }
+
+            let event = match self.receiver.try_recv() {
                 Ok(e) => e,
-                Err(_) => return
-            };
-            let is_resize = match event {
-                Event::WindowEvent{ event: WindowEvent::Resized(..), .. } => true,
-                _ => false
+                Err(TryRecvError::Disconnected) => return,
+                Err(TryRecvError::Empty) => {
+                    // All the queued events have been delivered. Report the end of the batch
+                    // before waiting, the callback may still change the way we wait.
+                    flow = callback(Event::EventsCleared);
+                    match flow {
+                        ControlFlow::Continue => match self.receiver.recv() {
+                            Ok(e) => e,
+                            Err(_) => return
+                        },
+                        ControlFlow::Poll => Event::ResumeTimeReached,
+                        ControlFlow::WaitUntil(deadline) => {
+                            let now = Instant::now();
+                            let timeout = if deadline > now { deadline - now } else { Duration::from_secs(0) };
+                            match self.receiver.recv_timeout(timeout) {
+                                Ok(e) => e,
+                                Err(RecvTimeoutError::Timeout) => Event::ResumeTimeReached,
+                                Err(RecvTimeoutError::Disconnected) => return
+                            }
+                        },
+                        ControlFlow::Break => break,
+                    }
+                },
             };
 
-            let flow = callback(event);
-            if is_resize {
-                let (ref mutex, ref cvar) = *self.win32_block_loop;
-                let mut block_thread = mutex.lock().unwrap();
-                *block_thread = false;
-                cvar.notify_all();
-            }
-            match flow {
-                ControlFlow::Continue => continue,
-                ControlFlow::Break => break,
-            }
+            flow = self.call_callback(event, &mut callback);
+        }
+    }
+
+    // Calls `callback` with `event`. The background thread is blocked while a `Resized` event is
+    // being processed, so we unblock it afterwards.
+    fn call_callback<F, R>(&self, event: Event, callback: &mut F) -> R
+        where F: FnMut(Event) -> R
+    {
+        let is_resize = match event {
+            Event::WindowEvent{ event: WindowEvent::Resized(..), .. } => true,
+            _ => false
+        };
+
+        let ret = callback(event);
+        if is_resize {
+            let (ref mutex, ref cvar) = *self.win32_block_loop;
+            let mut block_thread = mutex.lock().unwrap();
+            *block_thread = false;
+            cvar.notify_all();
         }
+        ret
     }
 
     pub fn create_proxy(&self) -> EventsLoopProxy {
@@ -334,8 +377,8 @@ pub unsafe extern "system" fn callback(window: winapi::HWND, msg: winapi::UINT,
 
         winapi::WM_SIZE => {
             use events::WindowEvent::Resized;
-            let w = winapi::LOWORD(lparam as winapi::DWORD) as u32;
-            let h = winapi::HIWORD(lparam as winapi::DWORD) as u32;
+            let w = winapi::LOWORD(lparam as winapi::DWORD) as f64;
+            let h = winapi::HIWORD(lparam as winapi::DWORD) as f64;
 
             // Wait for the parent thread to process the resize event before returning from the
             // callback.
@@ -345,7 +388,7 @@ pub unsafe extern "system" fn callback(window: winapi::HWND, msg: winapi::UINT,
 
                 let event = Event::WindowEvent {
                     window_id: SuperWindowId(WindowId(window)),
-                    event: Resized(w, h),
+                    event: Resized(LogicalSize::new(w, h)),
                 };
 
                 // If this window has been inserted into the window map, the resize event happened
@@ -372,11 +415,11 @@ pub unsafe extern "system" fn callback(window: winapi::HWND, msg: winapi::UINT,
 
         winapi::WM_MOVE => {
             use events::WindowEvent::Moved;
-            let x = winapi::LOWORD(lparam as winapi::DWORD) as i32;
-            let y = winapi::HIWORD(lparam as winapi::DWORD) as i32;
+            let x = winapi::LOWORD(lparam as winapi::DWORD) as i32 as f64;
+            let y = winapi::HIWORD(lparam as winapi::DWORD) as i32 as f64;
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
-                event: Moved(x, y),
+                event: Moved(LogicalPosition::new(x, y)),
             });
             0
         },
@@ -437,7 +480,7 @@ pub unsafe extern "system" fn callback(window: winapi::HWND, msg: winapi::UINT,
 
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
-                event: MouseMoved { device_id: DEVICE_ID, position: (x, y) },
+                event: MouseMoved { device_id: DEVICE_ID, position: LogicalPosition::new(x, y) },
             });
 
             0
@@ -750,15 +793,15 @@ pub unsafe extern "system" fn callback(window: winapi::HWND, msg: winapi::UINT,
                         let window_state = wstash.lock().unwrap();
 
                         match window_state.attributes.min_dimensions {
-                            Some((width, height)) => {
-                                (*mmi).min_track = winapi::POINT { x: width as i32, y: height as i32 };
+                            Some(size) => {
+                                (*mmi).min_track = winapi::POINT { x: size.width as i32, y: size.height as i32 };
                             },
                             None => { }
                         }
 
                         match window_state.attributes.max_dimensions {
-                            Some((width, height)) => {
-                                (*mmi).max_track = winapi::POINT { x: width as i32, y: height as i32 };
+                            Some(size) => {
+                                (*mmi).max_track = winapi::POINT { x: size.width as i32, y: size.height as i32 };
                             },
                             None => { }
                         }
@@ -30,7 +30,7 @@ pub struct MonitorId {
     dimensions: (u32, u32),
 
     /// DPI scaling factor.
-    hidpi_factor: f32,
+    hidpi_factor: f64,
 }
 
 // Send is not implemented for HMONITOR, we have to wrap it and implement it manually.
@@ -139,7 +139,74 @@ impl MonitorId {
     }
 
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         self.hidpi_factor
     }
+
+    #[inline]
+    pub fn get_video_modes(&self) -> Vec<::VideoMode> {
+        // TODO: enumerate the video modes
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<::GammaRamp> {
+        None
+    }
+
+    #[inline]
+    pub fn set_gamma_ramp(&self, _ramp: &::GammaRamp) -> Result<(), ()> {
+        Err(())
+    }
+
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline]
+    pub fn get_work_area(&self) -> Option<((i32, i32), (u32, u32))> {
+        None
+    }
+
+    #[inline]
+    pub fn get_transform(&self) -> ::MonitorTransform {
+        ::MonitorTransform::Normal
+    }
+
+    #[inline]
+    pub fn get_subpixel_order(&self) -> ::SubpixelOrder {
+        ::SubpixelOrder::Unknown
+    }
+
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        None
+    }
+
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        None
+    }
+
+    // What `PartialEq` and `Hash` of the root `MonitorId` are based on.
+    #[inline]
+    pub fn get_identity(&self) -> String {
+        self.get_stable_identifier()
+    }
+
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        self.get_native_identifier()
+    }
 }
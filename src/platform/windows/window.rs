@@ -21,6 +21,7 @@ use CursorState;
 use MouseCursor;
 use WindowAttributes;
 use MonitorId as RootMonitorId;
+use dpi::{LogicalPosition, LogicalSize};
 
 use dwmapi;
 use kernel32;
@@ -81,7 +82,7 @@ impl Window {
     }
 
     /// See the docs in the crate root file.
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         use std::mem;
 
         let mut placement: winapi::WINDOWPLACEMENT = unsafe { mem::zeroed() };
@@ -92,11 +93,12 @@ impl Window {
         }
 
         let ref rect = placement.rcNormalPosition;
-        Some((rect.left as i32, rect.top as i32))
+        Some(LogicalPosition::new(rect.left as f64, rect.top as f64))
     }
 
     /// See the docs in the crate root file.
-    pub fn set_position(&self, x: i32, y: i32) {
+    pub fn set_position(&self, position: LogicalPosition) {
+        let (x, y): (i32, i32) = position.into();
         unsafe {
             user32::SetWindowPos(self.window.0, ptr::null_mut(), x as raw::c_int, y as raw::c_int,
                                  0, 0, winapi::SWP_ASYNCWINDOWPOS | winapi::SWP_NOZORDER | winapi::SWP_NOSIZE);
@@ -106,36 +108,37 @@ impl Window {
 
     /// See the docs in the crate root file.
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         let mut rect: winapi::RECT = unsafe { mem::uninitialized() };
 
         if unsafe { user32::GetClientRect(self.window.0, &mut rect) } == 0 {
             return None
         }
 
-        Some((
-            (rect.right - rect.left) as u32,
-            (rect.bottom - rect.top) as u32
+        Some(LogicalSize::new(
+            (rect.right - rect.left) as f64,
+            (rect.bottom - rect.top) as f64
         ))
     }
 
     /// See the docs in the crate root file.
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
         let mut rect: winapi::RECT = unsafe { mem::uninitialized() };
 
         if unsafe { user32::GetWindowRect(self.window.0, &mut rect) } == 0 {
             return None
         }
 
-        Some((
-            (rect.right - rect.left) as u32,
-            (rect.bottom - rect.top) as u32
+        Some(LogicalSize::new(
+            (rect.right - rect.left) as f64,
+            (rect.bottom - rect.top) as f64
         ))
     }
 
     /// See the docs in the crate root file.
-    pub fn set_inner_size(&self, x: u32, y: u32) {
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        let (x, y): (u32, u32) = size.into();
         unsafe {
             // Calculate the outer size based upon the specified inner size
             let mut rect = winapi::RECT { top: 0, left: 0, bottom: y as winapi::LONG, right: x as winapi::LONG };
@@ -152,6 +155,46 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        // Applied when handling `WM_GETMINMAXINFO`
+        self.window_state.lock().unwrap().attributes.min_dimensions = dimensions;
+        self.apply_size_constraints();
+    }
+
+    #[inline]
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.window_state.lock().unwrap().attributes.max_dimensions = dimensions;
+        self.apply_size_constraints();
+    }
+
+    // Windows only queries the limits with `WM_GETMINMAXINFO` when the window is resized. Resizing
+    // it to its current size makes `DefWindowProc` clamp it immediately, as on the other platforms.
+    fn apply_size_constraints(&self) {
+        let mut rect: winapi::RECT = unsafe { mem::uninitialized() };
+        if unsafe { user32::GetWindowRect(self.window.0, &mut rect) } == 0 {
+            return;
+        }
+        unsafe {
+            user32::SetWindowPos(self.window.0, ptr::null_mut(), 0, 0,
+                rect.right - rect.left, rect.bottom - rect.top,
+                winapi::SWP_ASYNCWINDOWPOS | winapi::SWP_NOZORDER | winapi::SWP_NOREPOSITION | winapi::SWP_NOMOVE);
+        }
+    }
+
+    pub fn set_resizable(&self, resizable: bool) {
+        self.window_state.lock().unwrap().attributes.resizable = resizable;
+        unsafe {
+            let mut style = user32::GetWindowLongW(self.window.0, winapi::GWL_STYLE) as winapi::DWORD;
+            if resizable {
+                style |= winapi::WS_SIZEBOX | winapi::WS_MAXIMIZEBOX;
+            } else {
+                style &= !(winapi::WS_SIZEBOX | winapi::WS_MAXIMIZEBOX);
+            }
+            user32::SetWindowLongW(self.window.0, winapi::GWL_STYLE, style as winapi::LONG);
+        }
+    }
+
     // TODO: remove
     pub fn platform_display(&self) -> *mut ::libc::c_void {
         panic!()        // Deprecated function ; we don't care anymore
@@ -250,11 +293,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
+    pub fn hidpi_factor(&self) -> f64 {
         1.0
     }
 
-    pub fn set_cursor_position(&self, x: i32, y: i32) -> Result<(), ()> {
+    pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), ()> {
+        let (x, y): (i32, i32) = position.into();
         let mut point = winapi::POINT {
             x: x,
             y: y,
@@ -284,10 +328,42 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorId>) {
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        // HWND_TOPMOST and HWND_NOTOPMOST
+        let insert_after = (if always_on_top { -1isize } else { -2isize }) as winapi::HWND;
+        unsafe {
+            user32::SetWindowPos(self.window.0, insert_after, 0, 0, 0, 0,
+                                 winapi::SWP_ASYNCWINDOWPOS | winapi::SWP_NOMOVE | winapi::SWP_NOSIZE);
+        }
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        let command = if minimized { winapi::SW_MINIMIZE } else { winapi::SW_RESTORE };
+        unsafe {
+            user32::ShowWindow(self.window.0, command);
+        }
+    }
+
+    #[inline]
+    pub fn set_fullscreen(&self, _fullscreen: Option<::Fullscreen>) {
         unimplemented!()
     }
 
+    #[inline]
+    pub fn get_state(&self) -> ::WindowState {
+        let fullscreen = self.window_state.lock().unwrap().attributes.fullscreen.is_some();
+        unsafe {
+            ::WindowState {
+                maximized: user32::IsZoomed(self.window.0) != 0,
+                minimized: user32::IsIconic(self.window.0) != 0,
+                fullscreen: fullscreen,
+                focused: user32::GetForegroundWindow() == self.window.0,
+                visible: user32::IsWindowVisible(self.window.0) != 0,
+            }
+        }
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         unimplemented!()
@@ -327,15 +403,17 @@ unsafe fn init(window: WindowAttributes, pl_attribs: PlatformSpecificWindowBuild
     let class_name = register_window_class();
 
     // building a RECT object with coordinates
+    let (width, height): (u32, u32) = window.dimensions.map(Into::into).unwrap_or((1024, 768));
     let mut rect = winapi::RECT {
-        left: 0, right: window.dimensions.unwrap_or((1024, 768)).0 as winapi::LONG,
-        top: 0, bottom: window.dimensions.unwrap_or((1024, 768)).1 as winapi::LONG,
+        left: 0, right: width as winapi::LONG,
+        top: 0, bottom: height as winapi::LONG,
     };
 
     // switching to fullscreen if necessary
     // this means adjusting the window's position so that it overlaps the right monitor,
     //  and change the monitor's resolution if necessary
-    let fullscreen = if let Some(RootMonitorId { ref inner }) = window.fullscreen {
+    let fullscreen = if let Some(ref fullscreen) = window.fullscreen {
+        let RootMonitorId { ref inner } = *fullscreen.get_monitor();
         try!(switch_to_fullscreen(&mut rect, inner));
         true
     } else {
@@ -357,6 +435,12 @@ unsafe fn init(window: WindowAttributes, pl_attribs: PlatformSpecificWindowBuild
         (winapi::WS_EX_APPWINDOW | winapi::WS_EX_WINDOWEDGE,
             winapi::WS_OVERLAPPEDWINDOW | winapi::WS_CLIPSIBLINGS | winapi::WS_CLIPCHILDREN)
     };
+    let ex_style = if window.always_on_top { ex_style | winapi::WS_EX_TOPMOST } else { ex_style };
+    let style = if window.resizable {
+        style
+    } else {
+        style & !(winapi::WS_SIZEBOX | winapi::WS_MAXIMIZEBOX)
+    };
 
     // adjusting the window coordinates using the style
     user32::AdjustWindowRectEx(&mut rect, style, 0, ex_style);
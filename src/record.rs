@@ -0,0 +1,453 @@
+//! Recording of the events received from an `EventsLoop`, and replay of these recordings.
+//!
+//! A `Recorder` stores the events given to it along with the time at which they were received.
+//! The resulting `Recording` can be serialized when the `serde` feature of this crate is enabled.
+//! A `Player` then feeds the events of a recording to the same callback as the one given to
+//! `EventsLoop::poll_events` or `EventsLoop::run_forever`, which makes it possible to reproduce a
+//! sequence of inputs.
+//!
+//! `WindowId`s and `DeviceId`s are only meaningful while the `EventsLoop` that generated them
+//! exists. A recording identifies windows and devices by an index instead, in the order they
+//! first appeared. The `window_id` and `device_id` fields of the types of this module contain
+//! these indices.
+//!
+//! When replaying, each window index must be mapped to a window of the current session with
+//! `Player::map_window`. Devices get identifiers that are distinct from the ones of the current
+//! session.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use winit::{ControlFlow, Event, EventsLoop, Window, WindowEvent};
+//! use winit::record::{Player, Recorder};
+//!
+//! let mut events_loop = EventsLoop::new();
+//! let window = Window::new(&events_loop).unwrap();
+//!
+//! let mut recorder = Recorder::new();
+//! events_loop.run_forever(|event| {
+//!     recorder.record(&event);
+//!     match event {
+//!         Event::WindowEvent { event: WindowEvent::Closed, .. } => ControlFlow::Break,
+//!         _ => ControlFlow::Continue,
+//!     }
+//! });
+//! let window_index = recorder.window_index(window.id()).unwrap();
+//! let recording = recorder.into_recording();
+//!
+//! let window = Window::new(&events_loop).unwrap();
+//! let mut player = Player::new(recording);
+//! player.map_window(window_index, window.id());
+//! player.run_forever(|event: Event| {
+//!     println!("{:?}", event);
+//!     ControlFlow::Continue
+//! });
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use {AxisId, ControlFlow, DeviceEvent, ElementState, KeyboardInput, MouseButton, MouseScrollDelta};
+use {Readiness, Rect, Signal, TouchPhase, WindowState};
+use dpi::{LogicalPosition, LogicalSize};
+use platform;
+
+/// A sequence of recorded events.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording {
+    /// The events, in the order they were received.
+    pub events: Vec<RecordedEvent>,
+}
+
+/// An event of a `Recording`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedEvent {
+    /// The time elapsed between the creation of the `Recorder` and the reception of the event.
+    pub time: Duration,
+    pub event: Event,
+}
+
+/// Recorded form of `winit::Event`.
+///
+/// User events are not recorded, and neither are monitor events since the monitors of the
+/// recording session may not exist anymore when replaying.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Event {
+    WindowEvent {
+        window_id: u32,
+        event: WindowEvent,
+    },
+    DeviceEvent {
+        device_id: u32,
+        event: DeviceEvent,
+    },
+    Awakened,
+    FdReady { token: usize, readiness: Readiness },
+    Signal(Signal),
+    ResumeTimeReached,
+    NewEvents,
+    EventsCleared,
+    LoopDestroyed,
+    ConnectionLost,
+    Suspended(bool),
+}
+
+/// Recorded form of `winit::WindowEvent`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowEvent {
+    Resized(LogicalSize),
+    Moved(LogicalPosition),
+    Closed,
+    DroppedFile(PathBuf),
+    HoveredFile(PathBuf),
+    HoveredFileCancelled,
+    ReceivedCharacter(char),
+    Focused(bool),
+    StateChanged(WindowState),
+    KeyboardInput { device_id: u32, input: KeyboardInput },
+    MouseMoved { device_id: u32, position: LogicalPosition },
+    MouseEntered { device_id: u32 },
+    MouseLeft { device_id: u32 },
+    MouseWheel { device_id: u32, delta: MouseScrollDelta, phase: TouchPhase },
+    MouseInput { device_id: u32, state: ElementState, button: MouseButton },
+    TouchpadPressure { device_id: u32, pressure: f32, stage: i64 },
+    AxisMotion { device_id: u32, axis: AxisId, value: f64 },
+    Refresh,
+    Damaged(Vec<Rect>),
+    RedrawRequested,
+    Touch(Touch),
+    HiDPIFactorChanged(f64),
+}
+
+/// Recorded form of `winit::Touch`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Touch {
+    pub device_id: u32,
+    pub phase: TouchPhase,
+    pub location: LogicalPosition,
+    pub id: u64,
+}
+
+/// Records the events received by the callback of an `EventsLoop`.
+pub struct Recorder {
+    start: Instant,
+    windows: Vec<::WindowId>,
+    devices: Vec<::DeviceId>,
+    events: Vec<RecordedEvent>,
+}
+
+impl Recorder {
+    /// Starts a new recording. The time of the events is relative to the creation of the
+    /// `Recorder`.
+    pub fn new() -> Recorder {
+        Recorder {
+            start: Instant::now(),
+            windows: Vec::new(),
+            devices: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Records `event`. Meant to be called from the callback of the `EventsLoop`.
+    pub fn record<T>(&mut self, event: &::Event<T>) {
+        let time = self.start.elapsed();
+        if let Some(event) = self.convert_event(event) {
+            self.events.push(RecordedEvent {
+                time: time,
+                event: event,
+            });
+        }
+    }
+
+    /// Returns the index that identifies a window in the recording, or `None` if no event of
+    /// this window was recorded.
+    pub fn window_index(&self, window_id: ::WindowId) -> Option<u32> {
+        self.windows.iter().position(|&id| id == window_id).map(|index| index as u32)
+    }
+
+    /// Stops the recording and returns the recorded events.
+    pub fn into_recording(self) -> Recording {
+        Recording { events: self.events }
+    }
+
+    fn convert_event<T>(&mut self, event: &::Event<T>) -> Option<Event> {
+        Some(match *event {
+            ::Event::WindowEvent { window_id, ref event } => Event::WindowEvent {
+                window_id: index_of(&mut self.windows, window_id),
+                event: self.convert_window_event(event),
+            },
+            ::Event::DeviceEvent { device_id, ref event } => Event::DeviceEvent {
+                device_id: index_of(&mut self.devices, device_id),
+                event: event.clone(),
+            },
+            ::Event::Awakened => Event::Awakened,
+            ::Event::UserEvent(_) => return None,
+            ::Event::MonitorAdded(_) | ::Event::MonitorRemoved(_) | ::Event::MonitorChanged(_) => return None,
+            ::Event::FdReady { token, readiness } => Event::FdReady {
+                token: token,
+                readiness: readiness,
+            },
+            ::Event::Signal(signal) => Event::Signal(signal),
+            ::Event::ResumeTimeReached => Event::ResumeTimeReached,
+            ::Event::NewEvents => Event::NewEvents,
+            ::Event::EventsCleared => Event::EventsCleared,
+            ::Event::LoopDestroyed => Event::LoopDestroyed,
+            ::Event::ConnectionLost => Event::ConnectionLost,
+            ::Event::Suspended(suspended) => Event::Suspended(suspended),
+        })
+    }
+
+    fn convert_window_event(&mut self, event: &::WindowEvent) -> WindowEvent {
+        let devices = &mut self.devices;
+        match *event {
+            ::WindowEvent::Resized(size) => WindowEvent::Resized(size),
+            ::WindowEvent::Moved(position) => WindowEvent::Moved(position),
+            ::WindowEvent::Closed => WindowEvent::Closed,
+            ::WindowEvent::DroppedFile(ref path) => WindowEvent::DroppedFile(path.clone()),
+            ::WindowEvent::HoveredFile(ref path) => WindowEvent::HoveredFile(path.clone()),
+            ::WindowEvent::HoveredFileCancelled => WindowEvent::HoveredFileCancelled,
+            ::WindowEvent::ReceivedCharacter(c) => WindowEvent::ReceivedCharacter(c),
+            ::WindowEvent::Focused(focused) => WindowEvent::Focused(focused),
+            ::WindowEvent::StateChanged(state) => WindowEvent::StateChanged(state),
+            ::WindowEvent::KeyboardInput { device_id, input } => WindowEvent::KeyboardInput {
+                device_id: index_of(devices, device_id),
+                input: input,
+            },
+            ::WindowEvent::MouseMoved { device_id, position } => WindowEvent::MouseMoved {
+                device_id: index_of(devices, device_id),
+                position: position,
+            },
+            ::WindowEvent::MouseEntered { device_id } => WindowEvent::MouseEntered {
+                device_id: index_of(devices, device_id),
+            },
+            ::WindowEvent::MouseLeft { device_id } => WindowEvent::MouseLeft {
+                device_id: index_of(devices, device_id),
+            },
+            ::WindowEvent::MouseWheel { device_id, delta, phase } => WindowEvent::MouseWheel {
+                device_id: index_of(devices, device_id),
+                delta: delta,
+                phase: phase,
+            },
+            ::WindowEvent::MouseInput { device_id, state, button } => WindowEvent::MouseInput {
+                device_id: index_of(devices, device_id),
+                state: state,
+                button: button,
+            },
+            ::WindowEvent::TouchpadPressure { device_id, pressure, stage } => {
+                WindowEvent::TouchpadPressure {
+                    device_id: index_of(devices, device_id),
+                    pressure: pressure,
+                    stage: stage,
+                }
+            },
+            ::WindowEvent::AxisMotion { device_id, axis, value } => WindowEvent::AxisMotion {
+                device_id: index_of(devices, device_id),
+                axis: axis,
+                value: value,
+            },
+            ::WindowEvent::Refresh => WindowEvent::Refresh,
+            ::WindowEvent::Damaged(ref rects) => WindowEvent::Damaged(rects.clone()),
+            ::WindowEvent::RedrawRequested => WindowEvent::RedrawRequested,
+            ::WindowEvent::Touch(touch) => WindowEvent::Touch(Touch {
+                device_id: index_of(devices, touch.device_id),
+                phase: touch.phase,
+                location: touch.location,
+                id: touch.id,
+            }),
+            ::WindowEvent::HiDPIFactorChanged(factor) => WindowEvent::HiDPIFactorChanged(factor),
+        }
+    }
+}
+
+// Returns the index of `id` in `ids`, adding it at the end if it isn't there yet.
+fn index_of<I: PartialEq>(ids: &mut Vec<I>, id: I) -> u32 {
+    match ids.iter().position(|other| *other == id) {
+        Some(index) => index as u32,
+        None => {
+            ids.push(id);
+            (ids.len() - 1) as u32
+        },
+    }
+}
+
+/// Replays a `Recording`.
+pub struct Player {
+    events: VecDeque<RecordedEvent>,
+    windows: HashMap<u32, ::WindowId>,
+    // The instant corresponding to the start of the recording, set when the replay starts.
+    start: Option<Instant>,
+}
+
+impl Player {
+    /// Prepares the replay of `recording`.
+    pub fn new(recording: Recording) -> Player {
+        Player {
+            events: recording.events.into_iter().collect(),
+            windows: HashMap::new(),
+            start: None,
+        }
+    }
+
+    /// Replays the events of the window with the given index in the recording as events of
+    /// `window_id`.
+    ///
+    /// The events of the windows that aren't mapped are skipped.
+    pub fn map_window(&mut self, index: u32, window_id: ::WindowId) {
+        self.windows.insert(index, window_id);
+    }
+
+    /// Returns true if all the events of the recording have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Calls the callback for each event whose time has come, and returns.
+    ///
+    /// The replay starts at the first call of `poll_events` or `run_forever`, and the events are
+    /// replayed with the same timing as in the recording.
+    pub fn poll_events<T, F>(&mut self, mut callback: F)
+        where F: FnMut(::Event<T>)
+    {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let now = Instant::now();
+        while self.events.front().map_or(false, |event| start + event.time <= now) {
+            let event = self.events.pop_front().unwrap().event;
+            if let Some(event) = self.convert_event(event) {
+                callback(event);
+            }
+        }
+    }
+
+    /// Calls the callback for each event of the recording, waiting between the events like in
+    /// the recording, until the end of the recording or until the callback returns
+    /// `ControlFlow::Break`.
+    ///
+    /// The other values returned by the callback are ignored, since the timing of the events is
+    /// decided by the recording. No `Event::LoopDestroyed` is added to the recorded events.
+    pub fn run_forever<T, F>(&mut self, mut callback: F)
+        where F: FnMut(::Event<T>) -> ControlFlow
+    {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        while let Some(recorded) = self.events.pop_front() {
+            let deadline = start + recorded.time;
+            let now = Instant::now();
+            if deadline > now {
+                thread::sleep(deadline - now);
+            }
+
+            if let Some(event) = self.convert_event(recorded.event) {
+                if callback(event) == ControlFlow::Break {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Returns `None` if the event belongs to a window that isn't mapped.
+    fn convert_event<T>(&self, event: Event) -> Option<::Event<T>> {
+        Some(match event {
+            Event::WindowEvent { window_id, event } => ::Event::WindowEvent {
+                window_id: match self.windows.get(&window_id) {
+                    Some(&window_id) => window_id,
+                    None => return None,
+                },
+                event: convert_window_event(event),
+            },
+            Event::DeviceEvent { device_id, event } => ::Event::DeviceEvent {
+                device_id: replayed_device_id(device_id),
+                event: event,
+            },
+            Event::Awakened => ::Event::Awakened,
+            Event::FdReady { token, readiness } => ::Event::FdReady {
+                token: token,
+                readiness: readiness,
+            },
+            Event::Signal(signal) => ::Event::Signal(signal),
+            Event::ResumeTimeReached => ::Event::ResumeTimeReached,
+            Event::NewEvents => ::Event::NewEvents,
+            Event::EventsCleared => ::Event::EventsCleared,
+            Event::LoopDestroyed => ::Event::LoopDestroyed,
+            Event::ConnectionLost => ::Event::ConnectionLost,
+            Event::Suspended(suspended) => ::Event::Suspended(suspended),
+        })
+    }
+}
+
+fn convert_window_event(event: WindowEvent) -> ::WindowEvent {
+    match event {
+        WindowEvent::Resized(size) => ::WindowEvent::Resized(size),
+        WindowEvent::Moved(position) => ::WindowEvent::Moved(position),
+        WindowEvent::Closed => ::WindowEvent::Closed,
+        WindowEvent::DroppedFile(path) => ::WindowEvent::DroppedFile(path),
+        WindowEvent::HoveredFile(path) => ::WindowEvent::HoveredFile(path),
+        WindowEvent::HoveredFileCancelled => ::WindowEvent::HoveredFileCancelled,
+        WindowEvent::ReceivedCharacter(c) => ::WindowEvent::ReceivedCharacter(c),
+        WindowEvent::Focused(focused) => ::WindowEvent::Focused(focused),
+        WindowEvent::StateChanged(state) => ::WindowEvent::StateChanged(state),
+        WindowEvent::KeyboardInput { device_id, input } => ::WindowEvent::KeyboardInput {
+            device_id: replayed_device_id(device_id),
+            input: input,
+        },
+        WindowEvent::MouseMoved { device_id, position } => ::WindowEvent::MouseMoved {
+            device_id: replayed_device_id(device_id),
+            position: position,
+        },
+        WindowEvent::MouseEntered { device_id } => ::WindowEvent::MouseEntered {
+            device_id: replayed_device_id(device_id),
+        },
+        WindowEvent::MouseLeft { device_id } => ::WindowEvent::MouseLeft {
+            device_id: replayed_device_id(device_id),
+        },
+        WindowEvent::MouseWheel { device_id, delta, phase } => ::WindowEvent::MouseWheel {
+            device_id: replayed_device_id(device_id),
+            delta: delta,
+            phase: phase,
+        },
+        WindowEvent::MouseInput { device_id, state, button } => ::WindowEvent::MouseInput {
+            device_id: replayed_device_id(device_id),
+            state: state,
+            button: button,
+        },
+        WindowEvent::TouchpadPressure { device_id, pressure, stage } => {
+            ::WindowEvent::TouchpadPressure {
+                device_id: replayed_device_id(device_id),
+                pressure: pressure,
+                stage: stage,
+            }
+        },
+        WindowEvent::AxisMotion { device_id, axis, value } => ::WindowEvent::AxisMotion {
+            device_id: replayed_device_id(device_id),
+            axis: axis,
+            value: value,
+        },
+        WindowEvent::Refresh => ::WindowEvent::Refresh,
+        WindowEvent::Damaged(rects) => ::WindowEvent::Damaged(rects),
+        WindowEvent::RedrawRequested => ::WindowEvent::RedrawRequested,
+        WindowEvent::Touch(touch) => ::WindowEvent::Touch(::Touch {
+            device_id: replayed_device_id(touch.device_id),
+            phase: touch.phase,
+            location: touch.location,
+            id: touch.id,
+        }),
+        WindowEvent::HiDPIFactorChanged(factor) => ::WindowEvent::HiDPIFactorChanged(factor),
+    }
+}
+
+// The identifier of the device with the given index in a recording.
+#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+fn replayed_device_id(index: u32) -> ::DeviceId {
+    ::DeviceId(platform::DeviceId::replayed(index))
+}
+
+// The other platforms only have a single device identifier.
+#[cfg(not(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd")))]
+fn replayed_device_id(_index: u32) -> ::DeviceId {
+    ::DeviceId(platform::DeviceId)
+}
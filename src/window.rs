@@ -1,7 +1,12 @@
 use std::collections::vec_deque::IntoIter as VecDequeIter;
+use std::vec::IntoIter as VecIter;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use CreationError;
 use CursorState;
+use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use EventsLoop;
 use MouseCursor;
 use Window;
@@ -21,30 +26,31 @@ impl WindowBuilder {
         }
     }
 
-    /// Requests the window to be of specific dimensions.
-    ///
-    /// Width and height are in pixels.
+    /// Requests the client area of the window to be of specific dimensions.
     #[inline]
-    pub fn with_dimensions(mut self, width: u32, height: u32) -> WindowBuilder {
-        self.window.dimensions = Some((width, height));
+    pub fn with_dimensions(mut self, size: LogicalSize) -> WindowBuilder {
+        self.window.dimensions = Some(size);
         self
     }
 
     /// Sets a minimum dimension size for the window
-    ///
-    /// Width and height are in pixels.
     #[inline]
-    pub fn with_min_dimensions(mut self, width: u32, height: u32) -> WindowBuilder {
-        self.window.min_dimensions = Some((width, height));
+    pub fn with_min_dimensions(mut self, min_size: LogicalSize) -> WindowBuilder {
+        self.window.min_dimensions = Some(min_size);
         self
     }
 
     /// Sets a maximum dimension size for the window
-    ///
-    /// Width and height are in pixels.
     #[inline]
-    pub fn with_max_dimensions(mut self, width: u32, height: u32) -> WindowBuilder {
-        self.window.max_dimensions = Some((width, height));
+    pub fn with_max_dimensions(mut self, max_size: LogicalSize) -> WindowBuilder {
+        self.window.max_dimensions = Some(max_size);
+        self
+    }
+
+    /// Sets whether the window can be resized by the user.
+    #[inline]
+    pub fn with_resizable(mut self, resizable: bool) -> WindowBuilder {
+        self.window.resizable = resizable;
         self
     }
 
@@ -55,11 +61,11 @@ impl WindowBuilder {
         self
     }
 
-    /// Sets the window fullscreen state. None means a normal window, Some(MonitorId)
-    /// means a fullscreen window on that specific monitor
+    /// Sets the window fullscreen state. None means a normal window, Some(Fullscreen)
+    /// means a fullscreen window on a specific monitor, see `Fullscreen`.
     #[inline]
-    pub fn with_fullscreen(mut self, monitor: Option<MonitorId>) -> WindowBuilder {
-        self.window.fullscreen = monitor;
+    pub fn with_fullscreen(mut self, fullscreen: Option<Fullscreen>) -> WindowBuilder {
+        self.window.fullscreen = fullscreen;
         self
     }
 
@@ -91,6 +97,13 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets whether the window should stay above the other windows.
+    #[inline]
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> WindowBuilder {
+        self.window.always_on_top = always_on_top;
+        self
+    }
+
     /// Enables multitouch.
     #[inline]
     pub fn with_multitouch(mut self) -> WindowBuilder {
@@ -102,23 +115,32 @@ impl WindowBuilder {
     ///
     /// Error should be very rare and only occur in case of permission denied, incompatible system,
     /// out of memory, etc.
-    pub fn build(mut self, events_loop: &EventsLoop) -> Result<Window, CreationError> {
+    pub fn build<T>(mut self, events_loop: &EventsLoop<T>) -> Result<Window, CreationError> {
         // resizing the window to the dimensions of the monitor when fullscreen
         if self.window.dimensions.is_none() {
-            if let Some(ref monitor) = self.window.fullscreen {
-                self.window.dimensions = Some(monitor.get_dimensions());
+            if let Some(ref fullscreen) = self.window.fullscreen {
+                let dimensions = match *fullscreen {
+                    Fullscreen::Exclusive(ref mode) => mode.get_dimensions(),
+                    Fullscreen::Borderless(ref monitor) => monitor.get_dimensions(),
+                };
+                let hidpi_factor = fullscreen.get_monitor().get_hidpi_factor();
+                self.window.dimensions = Some(dimensions.to_logical(hidpi_factor));
             }
         }
 
         // default dimensions
         if self.window.dimensions.is_none() {
-            self.window.dimensions = Some((1024, 768));
+            self.window.dimensions = Some(LogicalSize::new(1024.0, 768.0));
         }
 
         // building
         let w = try!(platform::Window::new(&events_loop.events_loop, &self.window, &self.platform_specific));
 
-        Ok(Window { window: w })
+        Ok(Window {
+            window: w,
+            redraw_requests: Arc::downgrade(&events_loop.redraw_requests),
+            events_loop_proxy: events_loop.events_loop.create_proxy(),
+        })
     }
 }
 
@@ -130,7 +152,7 @@ impl Window {
     /// Error should be very rare and only occur in case of permission denied, incompatible system,
     ///  out of memory, etc.
     #[inline]
-    pub fn new(events_loop: &EventsLoop) -> Result<Window, CreationError> {
+    pub fn new<T>(events_loop: &EventsLoop<T>) -> Result<Window, CreationError> {
         let builder = WindowBuilder::new();
         builder.build(events_loop)
     }
@@ -177,7 +199,7 @@ impl Window {
     ///
     /// Returns `None` if the window no longer exists.
     #[inline]
-    pub fn get_position(&self) -> Option<(i32, i32)> {
+    pub fn get_position(&self) -> Option<LogicalPosition> {
         self.window.get_position()
     }
 
@@ -187,72 +209,70 @@ impl Window {
     ///
     /// This is a no-op if the window has already been closed.
     #[inline]
-    pub fn set_position(&self, x: i32, y: i32) {
-        self.window.set_position(x, y)
+    pub fn set_position(&self, position: LogicalPosition) {
+        self.window.set_position(position)
     }
 
-    /// Returns the size in pixels of the client area of the window.
+    /// Returns the size of the client area of the window.
     ///
     /// The client area is the content of the window, excluding the title bar and borders.
-    /// These are the dimensions that need to be supplied to `glViewport`.
+    /// Convert it to physical pixels with `hidpi_factor` to get the dimensions of the frame
+    /// buffer, which need to be supplied to `glViewport`.
     ///
     /// Returns `None` if the window no longer exists.
     #[inline]
-    pub fn get_inner_size(&self) -> Option<(u32, u32)> {
+    pub fn get_inner_size(&self) -> Option<LogicalSize> {
         self.window.get_inner_size()
     }
 
-    /// Returns the size in points of the client area of the window.
+    /// Returns the size of the window.
     ///
-    /// The client area is the content of the window, excluding the title bar and borders.
-    /// To get the dimensions of the frame buffer when calling `glViewport`, multiply with hidpi factor.
+    /// These dimensions include title bar and borders. If you don't want these, you should use
+    ///  use `get_inner_size` instead.
     ///
     /// Returns `None` if the window no longer exists.
-    ///
-    /// DEPRECATED
     #[inline]
-    #[deprecated]
-    pub fn get_inner_size_points(&self) -> Option<(u32, u32)> {
-        self.window.get_inner_size().map(|(x, y)| {
-            let hidpi = self.hidpi_factor();
-            ((x as f32 / hidpi) as u32, (y as f32 / hidpi) as u32)
-        })
+    pub fn get_outer_size(&self) -> Option<LogicalSize> {
+        self.window.get_outer_size()
     }
 
-    /// Returns the size in pixels of the client area of the window.
-    ///
-    /// The client area is the content of the window, excluding the title bar and borders.
-    /// These are the dimensions of the frame buffer, and the dimensions that you should use
-    ///  when you call `glViewport`.
+    /// Modifies the inner size of the window.
     ///
-    /// Returns `None` if the window no longer exists.
+    /// See `get_inner_size` for more information about the values.
     ///
-    /// DEPRECATED
+    /// This is a no-op if the window has already been closed.
     #[inline]
-    #[deprecated]
-    pub fn get_inner_size_pixels(&self) -> Option<(u32, u32)> {
-        self.window.get_inner_size()
+    pub fn set_inner_size(&self, size: LogicalSize) {
+        self.window.set_inner_size(size)
     }
 
-    /// Returns the size in pixels of the window.
+    /// Sets the minimum dimensions of the client area of the window, or removes them with `None`.
     ///
-    /// These dimensions include title bar and borders. If you don't want these, you should use
-    ///  use `get_inner_size` instead.
+    /// The window is resized immediately if its current size doesn't respect the new limit.
+    #[inline]
+    pub fn set_min_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.window.set_min_dimensions(dimensions)
+    }
+
+    /// Sets the maximum dimensions of the client area of the window, or removes them with `None`.
     ///
-    /// Returns `None` if the window no longer exists.
+    /// The window is resized immediately if its current size doesn't respect the new limit.
     #[inline]
-    pub fn get_outer_size(&self) -> Option<(u32, u32)> {
-        self.window.get_outer_size()
+    pub fn set_max_dimensions(&self, dimensions: Option<LogicalSize>) {
+        self.window.set_max_dimensions(dimensions)
     }
 
-    /// Modifies the inner size of the window.
+    /// Sets whether the window can be resized by the user. The window can still be resized with
+    /// `set_inner_size`, which also moves the limits of a window that can't be resized.
     ///
-    /// See `get_inner_size` for more information about the values.
+    /// ## Platform-specific
     ///
-    /// This is a no-op if the window has already been closed.
+    /// - On X11 and Wayland, the minimum and maximum dimensions of a window that can't be resized
+    ///   are both set to its current size, so the window manager may still let the user maximize
+    ///   it.
     #[inline]
-    pub fn set_inner_size(&self, x: u32, y: u32) {
-        self.window.set_inner_size(x, y)
+    pub fn set_resizable(&self, resizable: bool) {
+        self.window.set_resizable(resizable)
     }
 
     /// DEPRECATED. Gets the native platform specific display for this window.
@@ -279,18 +299,17 @@ impl Window {
         self.window.set_cursor(cursor);
     }
 
-    /// Returns the ratio between the backing framebuffer resolution and the
-    /// window size in screen pixels. This is typically one for a normal display
-    /// and two for a retina display.
+    /// Returns the ratio between physical and logical pixels for this window, see the `dpi`
+    /// module. This is typically one for a normal display and two for a retina display.
     #[inline]
-    pub fn hidpi_factor(&self) -> f32 {
+    pub fn hidpi_factor(&self) -> f64 {
         self.window.hidpi_factor()
     }
 
     /// Changes the position of the cursor in window coordinates.
     #[inline]
-    pub fn set_cursor_position(&self, x: i32, y: i32) -> Result<(), ()> {
-        self.window.set_cursor_position(x, y)
+    pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), ()> {
+        self.window.set_cursor_position(position)
     }
 
     /// Sets how winit handles the cursor. See the documentation of `CursorState` for details.
@@ -307,10 +326,78 @@ impl Window {
         self.window.set_maximized(maximized)
     }
 
-    /// Sets the window to fullscreen or back
+    /// Sets whether the window should stay above the other windows.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Has no effect on Wayland, iOS, Android and Emscripten.
+    #[inline]
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        self.window.set_always_on_top(always_on_top)
+    }
+
+    /// Minimizes the window, also called iconifying it, or restores it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Has no effect on Wayland, iOS, Android and Emscripten.
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        self.window.set_minimized(minimized)
+    }
+
+    /// Sets the window to fullscreen or back, see `Fullscreen`.
+    ///
+    /// The video mode changed by `Fullscreen::Exclusive` is restored when the window leaves
+    /// fullscreen or is dropped. The monitor must come from the `EventsLoop` of the window, the
+    /// request is ignored otherwise.
+    #[inline]
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.window.set_fullscreen(fullscreen)
+    }
+
+    /// Returns true if the window is maximized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Always returns false on iOS, Android and Emscripten.
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.window.get_state().maximized
+    }
+
+    /// Returns true if the window is minimized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Always returns false on Wayland, which doesn't tell clients when they are minimized, and
+    ///   on iOS, Android and Emscripten.
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        self.window.get_state().minimized
+    }
+
+    /// Returns true if the window is fullscreen.
+    #[inline]
+    pub fn is_fullscreen(&self) -> bool {
+        self.window.get_state().fullscreen
+    }
+
+    /// Returns true if the window has the keyboard focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.window.get_state().focused
+    }
+
+    /// Returns true if the window is shown. A minimized window is still visible.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Always returns true on Wayland, where windows can't be hidden, and on iOS, Android and
+    ///   Emscripten.
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<MonitorId>) {
-        self.window.set_fullscreen(monitor)
+    pub fn is_visible(&self) -> bool {
+        self.window.get_state().visible
     }
 
     /// Returns the current monitor the window is on or the primary monitor is nothing
@@ -319,6 +406,21 @@ impl Window {
         self.window.get_current_monitor()
     }
 
+    /// Requests a `WindowEvent::RedrawRequested` event for this window, and wakes up the
+    /// `EventsLoop` if it is waiting for events.
+    ///
+    /// The event is delivered at the end of the current batch of events, or of the next one if
+    /// the `EventsLoop` isn't processing events. Multiple requests are merged into a single event.
+    /// This method can be called from any thread.
+    ///
+    /// Does nothing if the `EventsLoop` no longer exists.
+    pub fn request_redraw(&self) {
+        if let Some(redraw_requests) = self.redraw_requests.upgrade() {
+            ::request_redraw(&redraw_requests, self.id());
+            let _ = self.events_loop_proxy.wakeup();
+        }
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.window.id())
@@ -347,6 +449,10 @@ impl Iterator for AvailableMonitorsIter {
 }
 
 /// Identifier for a monitor.
+///
+/// Two `MonitorId`s are equal if they have the same stable identifier (see
+/// `get_stable_identifier`), so a `MonitorId` can be matched with the one of the same monitor in
+/// another session.
 #[derive(Clone)]
 pub struct MonitorId {
     pub(crate) inner: platform::MonitorId
@@ -363,20 +469,292 @@ impl MonitorId {
 
     /// Returns the number of pixels currently displayed on the monitor.
     #[inline]
-    pub fn get_dimensions(&self) -> (u32, u32) {
-        self.inner.get_dimensions()
+    pub fn get_dimensions(&self) -> PhysicalSize {
+        self.inner.get_dimensions().into()
     }
 
     /// Returns the top-left corner position of the monitor relative to the larger full
     /// screen area.
     #[inline]
-    pub fn get_position(&self) -> (i32, i32) {
-        self.inner.get_position()
+    pub fn get_position(&self) -> PhysicalPosition {
+        self.inner.get_position().into()
     }
 
     /// Returns the ratio between the monitor's physical pixels and logical pixels.
     #[inline]
-    pub fn get_hidpi_factor(&self) -> f32 {
+    pub fn get_hidpi_factor(&self) -> f64 {
         self.inner.get_hidpi_factor()
     }
+
+    /// Returns the physical size of the monitor in millimeters.
+    ///
+    /// Returns `None` if the size is unknown, for example for projectors.
+    #[inline]
+    pub fn get_dimensions_mm(&self) -> Option<(u32, u32)> {
+        self.inner.get_dimensions_mm()
+    }
+
+    /// Returns the refresh rate of the current video mode of the monitor, in hertz.
+    #[inline]
+    pub fn get_refresh_rate(&self) -> Option<u16> {
+        self.inner.get_refresh_rate()
+    }
+
+    /// Returns the position and size of the part of the monitor that isn't covered by panels
+    /// and docks, in the same coordinates as `get_position`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - On X11, this is the `_NET_WORKAREA` of the window manager clipped to the monitor, or the
+    ///   whole monitor if the window manager doesn't set it. `_NET_WORKAREA` covers the whole X
+    ///   screen, so a panel on one monitor may also shrink the work area of the others.
+    /// - Returns `None` on the other platforms.
+    #[inline]
+    pub fn get_work_area(&self) -> Option<(PhysicalPosition, PhysicalSize)> {
+        self.inner.get_work_area().map(|(position, size)| (position.into(), size.into()))
+    }
+
+    /// Returns how the content of the monitor is rotated and flipped.
+    #[inline]
+    pub fn get_transform(&self) -> MonitorTransform {
+        self.inner.get_transform()
+    }
+
+    /// Returns the layout of the subpixels of the monitor.
+    #[inline]
+    pub fn get_subpixel_order(&self) -> SubpixelOrder {
+        self.inner.get_subpixel_order()
+    }
+
+    /// Returns the PNP ID of the manufacturer of the monitor, for example `DEL` or `SAM`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - On X11, this is read from the EDID of the monitor.
+    /// - On Wayland, this is the make reported by the compositor, which is free-form.
+    /// - Returns `None` on the other platforms.
+    #[inline]
+    pub fn get_manufacturer(&self) -> Option<String> {
+        self.inner.get_manufacturer()
+    }
+
+    /// Returns the model of the monitor, or its product code if it has no model name.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Same as `get_manufacturer`.
+    #[inline]
+    pub fn get_model(&self) -> Option<String> {
+        self.inner.get_model()
+    }
+
+    /// Returns the serial number of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only implemented on X11, for monitors whose EDID has a serial number.
+    #[inline]
+    pub fn get_serial(&self) -> Option<String> {
+        self.inner.get_serial()
+    }
+
+    /// Returns an identifier of the monitor that stays the same across hotplugs and sessions,
+    /// for example to save which monitor a window was opened on.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - On X11, this is made of the manufacturer, model and serial number of the monitor. The name
+    ///   of its connector is used in place of the serial number if there is none, and in place of
+    ///   all of them if the monitor has no EDID.
+    /// - On Wayland, this is made of the make, model and position of the monitor, so moving it
+    ///   changes its identifier.
+    /// - On the other platforms, this is the native identifier of the monitor.
+    #[inline]
+    pub fn get_stable_identifier(&self) -> String {
+        self.inner.get_stable_identifier()
+    }
+
+    /// Returns the gamma ramp of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only implemented on X11, returns `None` on the other platforms.
+    /// - Wayland: not implemented yet, it requires the wlr-gamma-control protocol.
+    #[inline]
+    pub fn get_gamma_ramp(&self) -> Option<GammaRamp> {
+        self.inner.get_gamma_ramp()
+    }
+
+    /// Sets the gamma ramp of the monitor. The ramp must have as many entries per channel as the
+    /// one returned by `get_gamma_ramp`.
+    ///
+    /// The original ramp of the monitor is restored when the last `EventsLoop` is dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only implemented on X11, returns `Err` on the other platforms.
+    /// - Wayland: not implemented yet, it requires the wlr-gamma-control protocol.
+    #[inline]
+    pub fn set_gamma_ramp(&self, ramp: &GammaRamp) -> Result<(), ()> {
+        self.inner.set_gamma_ramp(ramp)
+    }
+
+    /// Returns the video modes supported by the monitor, to be used with `Fullscreen::Exclusive`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only X11 and the headless backend report video modes, the list is empty on the other
+    ///   platforms.
+    #[inline]
+    pub fn video_modes(&self) -> VideoModesIter {
+        VideoModesIter {
+            data: self.inner.get_video_modes().into_iter(),
+        }
+    }
+}
+
+impl PartialEq for MonitorId {
+    #[inline]
+    fn eq(&self, other: &MonitorId) -> bool {
+        self.inner.get_identity() == other.inner.get_identity()
+    }
+}
+
+impl Eq for MonitorId {}
+
+impl Hash for MonitorId {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.get_identity().hash(state);
+    }
+}
+
+// Only the name is shown, the other properties can't be queried on every platform.
+impl fmt::Debug for MonitorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MonitorId")
+            .field("name", &self.get_name())
+            .finish()
+    }
+}
+
+/// An iterator for the video modes of a monitor.
+pub struct VideoModesIter {
+    data: VecIter<VideoMode>,
+}
+
+impl Iterator for VideoModesIter {
+    type Item = VideoMode;
+
+    #[inline]
+    fn next(&mut self) -> Option<VideoMode> {
+        self.data.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+/// A video mode of a monitor: its resolution, refresh rate and bit depth.
+#[derive(Clone, Debug)]
+pub struct VideoMode {
+    pub(crate) dimensions: (u32, u32),
+    pub(crate) refresh_rate: u16,
+    pub(crate) bit_depth: u16,
+    pub(crate) monitor: MonitorId,
+    // The identifier of the mode for the platform.
+    pub(crate) native_mode: u64,
+}
+
+impl VideoMode {
+    /// Returns the resolution of the video mode.
+    #[inline]
+    pub fn get_dimensions(&self) -> PhysicalSize {
+        self.dimensions.into()
+    }
+
+    /// Returns the refresh rate of the video mode, in hertz.
+    #[inline]
+    pub fn get_refresh_rate(&self) -> u16 {
+        self.refresh_rate
+    }
+
+    /// Returns the number of bits per pixel of the video mode.
+    #[inline]
+    pub fn get_bit_depth(&self) -> u16 {
+        self.bit_depth
+    }
+
+    /// Returns the monitor that supports this video mode.
+    #[inline]
+    pub fn get_monitor(&self) -> MonitorId {
+        self.monitor.clone()
+    }
+}
+
+/// The fullscreen state of a window.
+#[derive(Clone, Debug)]
+pub enum Fullscreen {
+    /// Switches the monitor of the video mode to this mode while the window covers it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only X11 switches the video mode. The other platforms behave as with `Borderless`.
+    /// - On X11, switching to a mode bigger than the current size of the X screen fails, in which
+    ///   case the window is made fullscreen without changing the mode.
+    Exclusive(VideoMode),
+
+    /// Covers the monitor with the window, without changing its video mode.
+    Borderless(MonitorId),
+}
+
+impl Fullscreen {
+    /// Returns the monitor covered by the window.
+    #[inline]
+    pub fn get_monitor(&self) -> &MonitorId {
+        match *self {
+            Fullscreen::Exclusive(ref mode) => &mode.monitor,
+            Fullscreen::Borderless(ref monitor) => monitor,
+        }
+    }
+}
+
+/// How the content of a monitor is transformed, for example for monitors mounted in portrait.
+///
+/// Rotations are counter-clockwise. The flipped variants are mirrored around the vertical axis
+/// before being rotated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MonitorTransform {
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+/// The layout of the subpixels of a monitor, used for subpixel antialiasing of text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SubpixelOrder {
+    Unknown,
+    /// The monitor has no subpixels, or they can't be addressed separately.
+    None,
+    HorizontalRgb,
+    HorizontalBgr,
+    VerticalRgb,
+    VerticalBgr,
+}
+
+/// The gamma ramp of a monitor: the intensity output for each input value of each channel.
+///
+/// `0` is no light and `65535` the full intensity of the channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GammaRamp {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
 }
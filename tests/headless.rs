@@ -0,0 +1,284 @@
+#![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+
+extern crate winit;
+
+use std::collections::HashSet;
+use std::thread;
+use winit::{ControlFlow, ElementState, Event, EventsLoop, Fullscreen, MonitorTransform, MouseButton,
+            WindowEvent, WindowState};
+use winit::dpi::{LogicalPosition, LogicalSize};
+use winit::os::unix::EventsLoopExt;
+
+#[test]
+fn injected_events_are_delivered() {
+    let mut events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let injector = events_loop.headless_injector().unwrap();
+    let window = winit::WindowBuilder::new()
+        .with_title("test")
+        .with_dimensions(LogicalSize::new(320.0, 240.0))
+        .build(&events_loop)
+        .unwrap();
+
+    assert_eq!(window.get_inner_size(), Some(LogicalSize::new(320.0, 240.0)));
+    assert_eq!(injector.title(window.id()).unwrap(), Some("test".to_owned()));
+
+    {
+        let injector = injector.clone();
+        let id = window.id();
+        thread::spawn(move || {
+            injector.mouse_input(id, ElementState::Pressed, MouseButton::Left).unwrap();
+            injector.resize(id, LogicalSize::new(640.0, 480.0)).unwrap();
+        });
+    }
+
+    let mut events = Vec::new();
+    events_loop.run_forever(|event| {
+        match event {
+            Event::WindowEvent { event: WindowEvent::MouseInput { state, button, .. }, .. } => {
+                events.push((state, button));
+                ControlFlow::Continue
+            },
+            Event::WindowEvent { event: WindowEvent::Resized(size), .. } => {
+                if size == LogicalSize::new(640.0, 480.0) {
+                    ControlFlow::Break
+                } else {
+                    ControlFlow::Continue
+                }
+            },
+            _ => ControlFlow::Continue,
+        }
+    });
+
+    assert_eq!(events, vec![(ElementState::Pressed, MouseButton::Left)]);
+    assert_eq!(window.get_inner_size(), Some(LogicalSize::new(640.0, 480.0)));
+}
+
+#[test]
+fn fullscreen_covers_the_monitor() {
+    let events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let injector = events_loop.headless_injector().unwrap();
+    let window = winit::WindowBuilder::new()
+        .with_dimensions(LogicalSize::new(320.0, 240.0))
+        .build(&events_loop)
+        .unwrap();
+
+    let monitor = events_loop.get_available_monitors().nth(1).unwrap();
+    window.set_fullscreen(Some(Fullscreen::Borderless(monitor.clone())));
+    let dpi_factor = monitor.get_hidpi_factor();
+    assert_eq!(window.get_position(), Some(monitor.get_position().to_logical(dpi_factor)));
+    assert_eq!(window.get_inner_size(), Some(monitor.get_dimensions().to_logical(dpi_factor)));
+    assert!(injector.fullscreen(window.id()).unwrap().is_some());
+
+    window.set_fullscreen(None);
+    assert_eq!(window.get_position(), Some(LogicalPosition::new(0.0, 0.0)));
+    assert_eq!(window.get_inner_size(), Some(LogicalSize::new(320.0, 240.0)));
+    assert!(injector.fullscreen(window.id()).unwrap().is_none());
+}
+
+#[test]
+fn exclusive_fullscreen_covers_the_monitor_of_the_mode() {
+    let events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let monitor = events_loop.get_available_monitors().nth(1).unwrap();
+    let mode = monitor.video_modes().next().unwrap();
+    assert_eq!(mode.get_dimensions(), monitor.get_dimensions());
+    assert_eq!(mode.get_monitor().get_name(), monitor.get_name());
+
+    let window = winit::WindowBuilder::new()
+        .with_fullscreen(Some(Fullscreen::Exclusive(mode)))
+        .build(&events_loop)
+        .unwrap();
+    let dpi_factor = monitor.get_hidpi_factor();
+    assert_eq!(window.get_position(), Some(monitor.get_position().to_logical(dpi_factor)));
+    assert_eq!(window.get_inner_size(), Some(monitor.get_dimensions().to_logical(dpi_factor)));
+}
+
+#[test]
+fn work_area_of_headless_monitors_is_the_whole_monitor() {
+    let events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    for monitor in events_loop.get_available_monitors() {
+        let work_area = (monitor.get_position(), monitor.get_dimensions());
+        assert_eq!(monitor.get_work_area(), Some(work_area));
+        assert_eq!(monitor.get_transform(), MonitorTransform::Normal);
+    }
+}
+
+#[test]
+fn monitors_can_be_compared_and_hashed() {
+    let events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let first: HashSet<_> = events_loop.get_available_monitors().collect();
+    let second: HashSet<_> = events_loop.get_available_monitors().collect();
+    assert_eq!(first.len(), events_loop.get_available_monitors().count());
+    assert_eq!(first, second);
+
+    let primary = events_loop.get_primary_monitor();
+    assert!(first.contains(&primary));
+    assert!(events_loop.get_available_monitors().nth(1).unwrap() != primary);
+
+    // The same monitor of another session is equal as well.
+    let other_session: EventsLoop = EventsLoop::new_headless().unwrap();
+    assert!(other_session.get_primary_monitor() == primary);
+}
+
+#[test]
+fn size_constraints_can_change_at_runtime() {
+    let events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let injector = events_loop.headless_injector().unwrap();
+    let window = winit::WindowBuilder::new()
+        .with_dimensions(LogicalSize::new(320.0, 240.0))
+        .with_resizable(false)
+        .build(&events_loop)
+        .unwrap();
+    assert_eq!(injector.is_resizable(window.id()).unwrap(), Some(false));
+
+    window.set_resizable(true);
+    assert_eq!(injector.is_resizable(window.id()).unwrap(), Some(true));
+
+    window.set_min_dimensions(Some(LogicalSize::new(400.0, 300.0)));
+    assert_eq!(window.get_inner_size(), Some(LogicalSize::new(400.0, 300.0)));
+    window.set_max_dimensions(Some(LogicalSize::new(500.0, 300.0)));
+    window.set_inner_size(LogicalSize::new(640.0, 480.0));
+    assert_eq!(window.get_inner_size(), Some(LogicalSize::new(500.0, 300.0)));
+
+    window.set_min_dimensions(None);
+    window.set_max_dimensions(None);
+    window.set_inner_size(LogicalSize::new(100.0, 100.0));
+    assert_eq!(window.get_inner_size(), Some(LogicalSize::new(100.0, 100.0)));
+}
+
+#[test]
+fn state_changes_are_reported() {
+    let mut events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let injector = events_loop.headless_injector().unwrap();
+    let window = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    assert!(window.is_visible());
+    assert!(!window.is_maximized() && !window.is_fullscreen() && !window.is_focused());
+
+    window.set_maximized(true);
+    injector.focus(window.id(), true).unwrap();
+    let monitor = events_loop.get_primary_monitor();
+    window.set_fullscreen(Some(Fullscreen::Borderless(monitor)));
+    window.hide();
+    assert!(window.is_maximized() && window.is_focused() && window.is_fullscreen());
+    assert!(!window.is_visible() && !window.is_minimized());
+
+    let mut states = Vec::new();
+    events_loop.poll_events(|event| {
+        if let Event::WindowEvent { event: WindowEvent::StateChanged(state), .. } = event {
+            states.push(state);
+        }
+    });
+    assert_eq!(states.len(), 4);
+    assert!(states[0].maximized && !states[0].focused);
+    assert!(states[1].focused && !states[1].fullscreen);
+    assert_eq!(states[3], WindowState {
+        maximized: true,
+        minimized: false,
+        fullscreen: true,
+        focused: true,
+        visible: false,
+    });
+}
+
+#[test]
+fn windows_can_be_minimized_and_restored() {
+    let mut events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let window = winit::WindowBuilder::new().build(&events_loop).unwrap();
+
+    window.set_minimized(true);
+    assert!(window.is_minimized() && window.is_visible());
+    window.set_minimized(false);
+    assert!(!window.is_minimized());
+
+    let mut minimized = Vec::new();
+    events_loop.poll_events(|event| {
+        if let Event::WindowEvent { event: WindowEvent::StateChanged(state), .. } = event {
+            minimized.push(state.minimized);
+        }
+    });
+    assert_eq!(minimized, vec![true, false]);
+}
+
+#[test]
+fn always_on_top_can_change_at_runtime() {
+    let events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let injector = events_loop.headless_injector().unwrap();
+    let window = winit::WindowBuilder::new()
+        .with_always_on_top(true)
+        .build(&events_loop)
+        .unwrap();
+    assert_eq!(injector.is_always_on_top(window.id()).unwrap(), Some(true));
+
+    window.set_always_on_top(false);
+    assert_eq!(injector.is_always_on_top(window.id()).unwrap(), Some(false));
+}
+
+// Returns the window events delivered for the events pushed by `inject`, with coalescing enabled.
+fn coalesced_events<F>(inject: F) -> Vec<WindowEvent>
+    where F: FnOnce(&winit::os::unix::HeadlessInjector, winit::WindowId)
+{
+    let mut events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    events_loop.set_coalescing(true);
+    let injector = events_loop.headless_injector().unwrap();
+    let window = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    inject(&injector, window.id());
+
+    let mut events = Vec::new();
+    events_loop.poll_events(|event| {
+        if let Event::WindowEvent { event, .. } = event {
+            events.push(event);
+        }
+    });
+    events
+}
+
+fn position(event: &WindowEvent) -> Option<LogicalPosition> {
+    match *event {
+        WindowEvent::MouseMoved { position, .. } => Some(position),
+        _ => None,
+    }
+}
+
+#[test]
+fn consecutive_events_are_coalesced() {
+    let events = coalesced_events(|injector, id| {
+        injector.mouse_moved(id, LogicalPosition::new(1.0, 1.0)).unwrap();
+        injector.mouse_moved(id, LogicalPosition::new(2.0, 2.0)).unwrap();
+        injector.resize(id, LogicalSize::new(100.0, 100.0)).unwrap();
+        injector.resize(id, LogicalSize::new(200.0, 200.0)).unwrap();
+    });
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(position(&events[0]), Some(LogicalPosition::new(2.0, 2.0)));
+    match events[1] {
+        WindowEvent::Resized(size) => assert_eq!(size, LogicalSize::new(200.0, 200.0)),
+        ref event => panic!("unexpected event {:?}", event),
+    }
+}
+
+#[test]
+fn coalescing_keeps_the_order_of_events() {
+    // A move isn't merged into one that was followed by another event.
+    let events = coalesced_events(|injector, id| {
+        injector.mouse_moved(id, LogicalPosition::new(1.0, 1.0)).unwrap();
+        injector.resize(id, LogicalSize::new(100.0, 100.0)).unwrap();
+        injector.mouse_moved(id, LogicalPosition::new(2.0, 2.0)).unwrap();
+    });
+    assert_eq!(events.len(), 3);
+    assert_eq!(position(&events[0]), Some(LogicalPosition::new(1.0, 1.0)));
+    assert_eq!(position(&events[2]), Some(LogicalPosition::new(2.0, 2.0)));
+
+    // Events that can't be coalesced are delivered after the pending ones.
+    let events = coalesced_events(|injector, id| {
+        injector.mouse_moved(id, LogicalPosition::new(1.0, 1.0)).unwrap();
+        injector.mouse_input(id, ElementState::Pressed, MouseButton::Left).unwrap();
+        injector.mouse_moved(id, LogicalPosition::new(2.0, 2.0)).unwrap();
+        injector.mouse_moved(id, LogicalPosition::new(3.0, 3.0)).unwrap();
+    });
+    assert_eq!(events.len(), 3);
+    assert_eq!(position(&events[0]), Some(LogicalPosition::new(1.0, 1.0)));
+    match events[1] {
+        WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => (),
+        ref event => panic!("unexpected event {:?}", event),
+    }
+    assert_eq!(position(&events[2]), Some(LogicalPosition::new(3.0, 3.0)));
+}
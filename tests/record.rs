@@ -0,0 +1,92 @@
+#![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd"))]
+
+extern crate winit;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use winit::{ControlFlow, ElementState, Event, EventsLoop, MouseButton, WindowEvent};
+use winit::dpi::{LogicalPosition, LogicalSize};
+use winit::os::unix::EventsLoopExt;
+use winit::record::{Player, Recorder, Recording};
+
+// Goes through the serialized form of the recording when it's available.
+#[cfg(feature = "serde")]
+fn serialize_and_back(recording: &Recording) -> Recording {
+    let serialized = serde_json::to_string(recording).unwrap();
+    serde_json::from_str(&serialized).unwrap()
+}
+
+#[cfg(not(feature = "serde"))]
+fn serialize_and_back(recording: &Recording) -> Recording {
+    recording.clone()
+}
+
+// The events of a recording without their timing.
+fn recorded_events(recording: &Recording) -> Vec<String> {
+    recording.events.iter().map(|recorded| format!("{:?}", recorded.event)).collect()
+}
+
+#[test]
+fn recorded_events_are_replayed() {
+    let mut events_loop: EventsLoop = EventsLoop::new_headless().unwrap();
+    let injector = events_loop.headless_injector().unwrap();
+    let first = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    let second = winit::WindowBuilder::new().build(&events_loop).unwrap();
+
+    injector.mouse_moved(first.id(), LogicalPosition::new(10.0, 20.0)).unwrap();
+    injector.mouse_input(second.id(), ElementState::Pressed, MouseButton::Left).unwrap();
+    injector.received_character(first.id(), 'a').unwrap();
+    injector.resize(second.id(), LogicalSize::new(640.0, 480.0)).unwrap();
+
+    let mut recorder = Recorder::new();
+    let mut window_events = 0;
+    events_loop.poll_events(|event| {
+        recorder.record(&event);
+        if let Event::WindowEvent { .. } = event {
+            window_events += 1;
+        }
+    });
+    assert_eq!(window_events, 4);
+    let first_index = recorder.window_index(first.id()).unwrap();
+    let second_index = recorder.window_index(second.id()).unwrap();
+    assert_eq!((first_index, second_index), (0, 1));
+    let recording = recorder.into_recording();
+
+    let replayed_first = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    let replayed_second = winit::WindowBuilder::new().build(&events_loop).unwrap();
+    let mut player = Player::new(serialize_and_back(&recording));
+    player.map_window(first_index, replayed_first.id());
+    player.map_window(second_index, replayed_second.id());
+
+    let mut replayed: Vec<Event> = Vec::new();
+    player.run_forever(|event| {
+        replayed.push(event);
+        ControlFlow::Continue
+    });
+    assert!(player.is_finished());
+
+    // Recording the replayed events gives back the original recording.
+    let mut recorder = Recorder::new();
+    for event in &replayed {
+        recorder.record(event);
+    }
+    assert_eq!(recorded_events(&recorder.into_recording()), recorded_events(&recording));
+
+    let window_ids: Vec<_> = replayed.iter().filter_map(|event| match *event {
+        Event::WindowEvent { window_id, .. } => Some(window_id),
+        _ => None,
+    }).collect();
+    assert_eq!(window_ids, vec![replayed_first.id(), replayed_second.id(),
+                                replayed_first.id(), replayed_second.id()]);
+
+    // The injected events all come from the same device, which is replayed as a device that
+    // doesn't exist in the current session.
+    let device_ids: Vec<_> = replayed.iter().filter_map(|event| match *event {
+        Event::WindowEvent { event: WindowEvent::MouseMoved { device_id, .. }, .. } |
+        Event::WindowEvent { event: WindowEvent::MouseInput { device_id, .. }, .. } => Some(device_id),
+        _ => None,
+    }).collect();
+    assert_eq!(device_ids.len(), 2);
+    assert_eq!(device_ids[0], device_ids[1]);
+    assert!(device_ids[0] != injector.device_id());
+}
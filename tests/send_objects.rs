@@ -6,6 +6,7 @@ fn needs_send<T:Send>() {}
 fn events_loop_proxy_send() {
     // ensures that `winit::EventsLoopProxy` implements `Send`
     needs_send::<winit::EventsLoopProxy>();
+    needs_send::<winit::EventsLoopProxy<String>>();
 }
 
 #[test]